@@ -0,0 +1,49 @@
+//! Compares creating grouped entities one at a time with creating them in bulk.
+//!
+//! Run with `cargo bench --bench extend`.
+
+#![allow(dead_code)]
+
+use sparsey::World;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ENTITY_COUNT: u32 = 100_000;
+const ITERATIONS: u32 = 20;
+
+struct A(u32);
+struct B(u32);
+struct C(u32);
+
+fn create_world() -> World {
+    World::builder()
+        .add_group::<(A, B)>()
+        .add_group::<(A, B, C)>()
+        .build()
+}
+
+fn bench(name: &str, mut f: impl FnMut(&mut World)) {
+    let mut total = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let mut world = create_world();
+        let start = Instant::now();
+        f(&mut world);
+        total += start.elapsed();
+        black_box(world);
+    }
+
+    println!("{name:<12} {:?}", total / ITERATIONS);
+}
+
+fn main() {
+    bench("create", |world| {
+        for i in 0..ENTITY_COUNT {
+            world.create((A(i), B(i), C(i)));
+        }
+    });
+
+    bench("extend", |world| {
+        world.extend((0..ENTITY_COUNT).map(|i| (A(i), B(i), C(i))));
+    });
+}
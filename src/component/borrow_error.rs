@@ -0,0 +1,22 @@
+use core::{error, fmt};
+
+/// Error returned by [`World::try_borrow`](crate::World::try_borrow) and
+/// [`World::try_borrow_mut`](crate::World::try_borrow_mut).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BorrowError {
+    /// The component is not registered.
+    Unregistered,
+    /// The component is already borrowed in a conflicting way.
+    AlreadyBorrowed,
+}
+
+impl fmt::Display for BorrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unregistered => f.write_str("Component is not registered"),
+            Self::AlreadyBorrowed => f.write_str("Component is already borrowed"),
+        }
+    }
+}
+
+impl error::Error for BorrowError {}
@@ -1,10 +1,17 @@
-use crate::component::{group, panic_missing_comp, ungroup, Component, GroupMask};
+use crate::component::{
+    group, group_extend, panic_missing_comp, ungroup, Component, ComponentData, GroupMask,
+};
 use crate::entity::Entity;
 use crate::World;
 use core::any::TypeId;
 
 /// Handles insert and remove operations for components stored in a [`World`].
 ///
+/// Implemented for tuples of up to 16 components. A single component must be
+/// wrapped in a one-element tuple, such as `(Position(0, 0),)`, because tuples
+/// of components are themselves valid [`Component`] types and a bare component
+/// impl would overlap with the tuple impls.
+///
 /// # Safety
 ///
 /// This trait is considered an implementation detail and cannot be safely
@@ -14,7 +21,9 @@ pub unsafe trait ComponentSet {
     type Remove;
 
     /// Adds the given `components` to `entity`.
-    unsafe fn insert(world: &mut World, entity: Entity, components: Self);
+    ///
+    /// Returns the components that were replaced.
+    unsafe fn insert(world: &mut World, entity: Entity, components: Self) -> Self::Remove;
 
     /// Creates new entities from the components produced by the iterator.
     ///
@@ -23,12 +32,31 @@ pub unsafe trait ComponentSet {
     where
         TComponents: IntoIterator<Item = Self>;
 
+    /// Returns whether `entity` has any of the components. Unregistered
+    /// components are treated as missing.
+    #[must_use]
+    fn contains_any(world: &World, entity: Entity) -> bool;
+
+    /// Returns whether `entity` has all of the components. Unregistered
+    /// components are treated as missing.
+    #[must_use]
+    fn contains_all(world: &World, entity: Entity) -> bool;
+
     /// Removes components from the given `entity`.
     ///
     /// Returns the components that were successfully removed.
     #[must_use = "Use `delete` to discard the components."]
     unsafe fn remove(world: &mut World, entity: Entity) -> Self::Remove;
 
+    /// Removes components from the given `entity`.
+    ///
+    /// Returns the components only if all of them were present. Otherwise,
+    /// the `entity` is left unchanged.
+    #[must_use = "Use `delete` to discard the components."]
+    unsafe fn take(world: &mut World, entity: Entity) -> Option<Self>
+    where
+        Self: Sized;
+
     /// Removes components from the given `entity`.
     unsafe fn delete(world: &mut World, entity: Entity);
 }
@@ -41,10 +69,14 @@ macro_rules! impl_component_set {
         {
             type Remove = ($(Option<$Comp>,)*);
 
-            unsafe fn insert(world: &mut World, entity: Entity, components: Self) {
+            unsafe fn insert(world: &mut World, entity: Entity, components: Self) -> Self::Remove {
+                if world.components.auto_register {
+                    $(world.components.register_dyn(ComponentData::new::<$Comp>());)*
+                }
+
                 let mut group_mask = GroupMask::EMPTY;
 
-                $({
+                let replaced = ($({
                     let metadata = world
                         .components
                         .metadata
@@ -59,9 +91,9 @@ macro_rules! impl_component_set {
                             .components
                             .get_unchecked_mut(metadata.storage_index)
                             .get_mut()
-                            .insert(entity, components.$idx);
+                            .insert(entity, components.$idx)
                     }
-                })*
+                },)*);
 
                 if group_mask != GroupMask::EMPTY {
                     unsafe {
@@ -73,12 +105,48 @@ macro_rules! impl_component_set {
                         );
                     }
                 }
+
+                replaced
+            }
+
+            fn contains_any(world: &World, entity: Entity) -> bool {
+                $(world
+                    .components
+                    .metadata
+                    .get(&TypeId::of::<$Comp>())
+                    .is_some_and(|metadata| unsafe {
+                        world
+                            .components
+                            .components
+                            .get_unchecked(metadata.storage_index)
+                            .borrow()
+                            .contains(entity)
+                    }))||*
+            }
+
+            fn contains_all(world: &World, entity: Entity) -> bool {
+                $(world
+                    .components
+                    .metadata
+                    .get(&TypeId::of::<$Comp>())
+                    .is_some_and(|metadata| unsafe {
+                        world
+                            .components
+                            .components
+                            .get_unchecked(metadata.storage_index)
+                            .borrow()
+                            .contains(entity)
+                    }))&&*
             }
 
             fn extend<TComponents>(world: &mut World, components: TComponents) -> &[Entity]
             where
                 TComponents: IntoIterator<Item = Self>,
             {
+                if world.components.auto_register {
+                    $(world.components.register_dyn(ComponentData::new::<$Comp>());)*
+                }
+
                 let mut group_mask = GroupMask::EMPTY;
 
                 let sparse_sets = ($({
@@ -99,6 +167,7 @@ macro_rules! impl_component_set {
                     }
                 },)*);
 
+                world.entities.maintain();
                 let start_entity = world.entities.len();
 
                 components.into_iter().for_each(|components| {
@@ -114,15 +183,13 @@ macro_rules! impl_component_set {
                 };
 
                 if group_mask != GroupMask::EMPTY {
-                    for &entity in new_entities {
-                        unsafe {
-                            group(
-                                &mut world.components.components,
-                                &mut world.components.groups,
-                                group_mask,
-                                entity,
-                            );
-                        }
+                    unsafe {
+                        group_extend(
+                            &mut world.components.components,
+                            &mut world.components.groups,
+                            group_mask,
+                            new_entities,
+                        );
                     }
                 }
 
@@ -166,6 +233,15 @@ macro_rules! impl_component_set {
                 }
             }
 
+            unsafe fn take(world: &mut World, entity: Entity) -> Option<Self> {
+                if !Self::contains_all(world, entity) {
+                    return None;
+                }
+
+                let components = unsafe { Self::remove(world, entity) };
+                Some(($(components.$idx?,)*))
+            }
+
             unsafe fn delete(world: &mut World, entity: Entity) {
                 let mut group_mask = GroupMask::EMPTY;
 
@@ -210,14 +286,25 @@ unsafe impl ComponentSet for () {
     type Remove = ();
 
     #[inline(always)]
-    unsafe fn insert(_world: &mut World, _entity: Entity, _components: Self) {
+    unsafe fn insert(_world: &mut World, _entity: Entity, _components: Self) -> Self::Remove {
         // Empty
     }
 
+    #[inline]
+    fn contains_any(_world: &World, _entity: Entity) -> bool {
+        false
+    }
+
+    #[inline]
+    fn contains_all(_world: &World, _entity: Entity) -> bool {
+        true
+    }
+
     fn extend<TComponents>(world: &mut World, components: TComponents) -> &[Entity]
     where
         TComponents: IntoIterator<Item = Self>,
     {
+        world.entities.maintain();
         let start_entity = world.entities.len();
 
         components.into_iter().for_each(|()| {
@@ -232,6 +319,11 @@ unsafe impl ComponentSet for () {
         // Empty
     }
 
+    #[inline]
+    unsafe fn take(_world: &mut World, _entity: Entity) -> Option<Self> {
+        Some(())
+    }
+
     #[inline(always)]
     unsafe fn delete(_world: &mut World, _entity: Entity) {
         // Empty
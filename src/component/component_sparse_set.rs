@@ -1,6 +1,7 @@
 use crate::component::Component;
-use crate::entity::{Entity, SparseVec, SparseVecSlot};
+use crate::entity::{Entity, EntityRemap, SparseVec, SparseVecSlot, StorageMemory};
 use alloc::{alloc, Layout, LayoutError};
+use core::any::{self, TypeId};
 use core::ptr::NonNull;
 use core::{fmt, mem, slice};
 
@@ -33,40 +34,41 @@ impl ComponentSparseSet {
     where
         T: Component,
     {
+        self.debug_assert_type::<T>();
+
         let slot = self.sparse.get_mut_or_allocate_at(entity.sparse());
 
-        match slot {
-            Some(slot) => {
-                let index = slot.dense();
+        if let Some(slot) = slot {
+            let index = slot.dense();
 
-                // Replace existing entity and component.
-                *self.entities.add(index).as_mut() = entity;
-                Some(self.components.cast::<T>().add(index).replace(component))
-            }
-            None => {
-                *slot = Some(SparseVecSlot {
-                    index: self.len as u32,
-                    version: entity.version,
-                });
-
-                if self.len == self.cap {
-                    self.grow();
-                }
+            // Replace existing entity and component.
+            *self.entities.add(index).as_mut() = entity;
+            return Some(self.components.cast::<T>().add(index).replace(component));
+        }
 
-                // Write entity and component to uninitialized memory.
-                self.entities.add(self.len).write(entity);
-                self.components.cast::<T>().add(self.len).write(component);
+        *slot = Some(SparseVecSlot {
+            index: self.len as u32,
+            version: entity.version,
+        });
 
-                self.len += 1;
-                None
-            }
+        if self.len == self.cap {
+            self.grow();
         }
+
+        // Write entity and component to uninitialized memory.
+        self.entities.add(self.len).write(entity);
+        self.components.cast::<T>().add(self.len).write(component);
+
+        self.len += 1;
+        None
     }
 
     pub unsafe fn remove<T>(&mut self, entity: Entity) -> Option<T>
     where
         T: Component,
     {
+        self.debug_assert_type::<T>();
+
         let raw_index = self.sparse.remove(entity)?;
         let index = raw_index as usize;
         self.len -= 1;
@@ -94,6 +96,8 @@ impl ComponentSparseSet {
     where
         T: Component,
     {
+        self.debug_assert_type::<T>();
+
         let Some(raw_index) = self.sparse.remove(entity) else {
             return;
         };
@@ -118,6 +122,19 @@ impl ComponentSparseSet {
         last_ptr.copy_to(dropped_ptr, 1);
     }
 
+    #[inline]
+    #[must_use]
+    pub fn new_empty(&self) -> Self {
+        (self.vtable.new_empty)()
+    }
+
+    #[inline]
+    pub unsafe fn drain_into(&mut self, other: &mut Self, remap: &EntityRemap) {
+        unsafe {
+            (self.vtable.drain_into)(self, other, remap);
+        }
+    }
+
     #[inline]
     pub fn delete_dyn(&mut self, entity: Entity) {
         unsafe {
@@ -131,16 +148,21 @@ impl ComponentSparseSet {
     where
         T: Component,
     {
+        self.debug_assert_type::<T>();
+
         let dense = self.sparse.get(entity)? as usize;
         Some(self.components.cast::<T>().add(dense).as_ref())
     }
 
     #[inline]
     #[must_use]
+    #[allow(clippy::mut_from_ref)]
     pub unsafe fn get_mut<T>(&self, entity: Entity) -> Option<&mut T>
     where
         T: Component,
     {
+        self.debug_assert_type::<T>();
+
         let dense = self.sparse.get(entity)? as usize;
         Some(self.components.cast::<T>().add(dense).as_mut())
     }
@@ -169,6 +191,8 @@ impl ComponentSparseSet {
     where
         T: Component,
     {
+        self.debug_assert_type::<T>();
+
         slice::from_raw_parts(self.components.cast::<T>().as_ptr(), self.len)
     }
 
@@ -178,15 +202,33 @@ impl ComponentSparseSet {
     where
         T: Component,
     {
+        self.debug_assert_type::<T>();
+
         slice::from_raw_parts_mut(self.components.cast::<T>().as_ptr(), self.len)
     }
 
+    #[inline]
+    #[must_use]
+    pub unsafe fn split_mut<T>(&mut self) -> (&[Entity], &mut [T])
+    where
+        T: Component,
+    {
+        self.debug_assert_type::<T>();
+
+        (
+            slice::from_raw_parts(self.entities.as_ptr(), self.len),
+            slice::from_raw_parts_mut(self.components.cast::<T>().as_ptr(), self.len),
+        )
+    }
+
     #[inline]
     #[must_use]
     pub unsafe fn as_non_null_ptr<T>(&self) -> NonNull<T>
     where
         T: Component,
     {
+        self.debug_assert_type::<T>();
+
         self.components.cast::<T>()
     }
 
@@ -202,6 +244,40 @@ impl ComponentSparseSet {
         self.len == 0
     }
 
+    #[inline]
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        (self.vtable.type_name)()
+    }
+
+    #[must_use]
+    pub fn memory_usage(&self) -> StorageMemory {
+        let slot_size = mem::size_of::<Option<SparseVecSlot>>();
+        let item_size = mem::size_of::<Entity>() + self.vtable.component_size;
+
+        StorageMemory {
+            type_name: (self.vtable.type_name)(),
+            len: self.len,
+            capacity: self.cap,
+            used_bytes: self.sparse.len() * slot_size + self.len * item_size,
+            allocated_bytes: self.sparse.capacity() * slot_size + self.cap * item_size,
+        }
+    }
+
+    #[inline]
+    fn debug_assert_type<T>(&self)
+    where
+        T: Component,
+    {
+        debug_assert_eq!(
+            (self.vtable.type_id)(),
+            TypeId::of::<T>(),
+            "Sparse set of '{}' accessed as '{}'",
+            (self.vtable.type_name)(),
+            any::type_name::<T>(),
+        );
+    }
+
     #[inline]
     pub unsafe fn swap(&mut self, a: usize, b: usize) {
         unsafe {
@@ -223,23 +299,61 @@ impl ComponentSparseSet {
         }
     }
 
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.sparse.shrink_to_fit();
+
+        unsafe {
+            (self.vtable.shrink_to_fit)(self);
+        }
+    }
+
     #[cold]
     #[inline(never)]
     unsafe fn grow_typed<T>(&mut self)
     where
         T: Component,
     {
-        // Allocate new storage for entities and components.
-        let (new_entities, new_components, new_cap) = {
-            let new_cap = match self.cap {
-                0 => 4,
-                cap => {
-                    let new_cap = cap.saturating_add(cap);
-                    assert_ne!(new_cap, self.cap, "Cannot grow sparse set");
-                    new_cap
-                }
-            };
+        let new_cap = match self.cap {
+            0 => 4,
+            cap => {
+                let new_cap = cap.saturating_add(cap);
+                assert_ne!(new_cap, self.cap, "Cannot grow sparse set");
+                new_cap
+            }
+        };
+
+        self.realloc_typed::<T>(new_cap);
+    }
+
+    unsafe fn shrink_to_fit_typed<T>(&mut self)
+    where
+        T: Component,
+    {
+        if self.len == self.cap {
+            return;
+        }
+
+        if self.len == 0 {
+            let (layout, _) = Self::compute_layout::<T>(self.cap);
+            alloc::dealloc(self.entities.cast().as_ptr(), layout);
+
+            self.entities = NonNull::dangling();
+            self.components = NonNull::<T>::dangling().cast();
+            self.cap = 0;
+        } else {
+            self.realloc_typed::<T>(self.len);
+        }
+    }
+
+    unsafe fn realloc_typed<T>(&mut self, new_cap: usize)
+    where
+        T: Component,
+    {
+        debug_assert!(new_cap >= self.len);
 
+        // Allocate new storage for entities and components.
+        let (new_entities, new_components) = {
             let (new_layout, new_components_offset) = Self::compute_layout::<T>(new_cap);
 
             let Some(new_data) = NonNull::new(alloc::alloc(new_layout)) else {
@@ -249,7 +363,6 @@ impl ComponentSparseSet {
             (
                 new_data.cast::<Entity>(),
                 new_data.byte_add(new_components_offset).cast::<T>(),
-                new_cap,
             )
         };
 
@@ -293,6 +406,28 @@ impl ComponentSparseSet {
         mem::swap(component_a, component_b);
     }
 
+    unsafe fn drain_into_typed<T>(&mut self, other: &mut Self, remap: &EntityRemap)
+    where
+        T: Component,
+    {
+        let len = self.len;
+
+        // Forget the components first so a panic can only leak them.
+        self.sparse.clear();
+        self.len = 0;
+
+        for i in 0..len {
+            unsafe {
+                let entity = *self.entities.add(i).as_ref();
+                let component = self.components.cast::<T>().add(i).read();
+
+                if let Some(entity) = remap.get(entity) {
+                    other.insert(entity, component);
+                }
+            }
+        }
+    }
+
     unsafe fn clear_typed<T>(&mut self)
     where
         T: Component,
@@ -369,7 +504,13 @@ impl fmt::Debug for ComponentSparseSet {
 
 #[derive(Clone, Copy)]
 struct ComponentSparseSetVtable {
+    type_id: fn() -> TypeId,
+    type_name: fn() -> &'static str,
+    component_size: usize,
+    new_empty: fn() -> ComponentSparseSet,
+    drain_into: unsafe fn(&mut ComponentSparseSet, &mut ComponentSparseSet, &EntityRemap),
     grow: unsafe fn(&mut ComponentSparseSet),
+    shrink_to_fit: unsafe fn(&mut ComponentSparseSet),
     swap: unsafe fn(&mut ComponentSparseSet, usize, usize),
     delete: unsafe fn(&mut ComponentSparseSet, Entity),
     clear: unsafe fn(&mut ComponentSparseSet),
@@ -382,7 +523,13 @@ impl ComponentSparseSetVtable {
         T: Component,
     {
         Self {
+            type_id: TypeId::of::<T>,
+            type_name: any::type_name::<T>,
+            component_size: mem::size_of::<T>(),
+            new_empty: ComponentSparseSet::new::<T>,
+            drain_into: ComponentSparseSet::drain_into_typed::<T>,
             grow: ComponentSparseSet::grow_typed::<T>,
+            shrink_to_fit: ComponentSparseSet::shrink_to_fit_typed::<T>,
             swap: ComponentSparseSet::swap_typed::<T>,
             delete: ComponentSparseSet::delete::<T>,
             clear: ComponentSparseSet::clear_typed::<T>,
@@ -390,3 +537,15 @@ impl ComponentSparseSetVtable {
         }
     }
 }
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic = "accessed as 'u32'"]
+    fn test_mismatched_type() {
+        let sparse_set = ComponentSparseSet::new::<u8>();
+        let _ = unsafe { sparse_set.get::<u32>(Entity::with_index(0)) };
+    }
+}
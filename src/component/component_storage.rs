@@ -1,9 +1,10 @@
 use crate::component::{
-    group, ungroup_all, Component, ComponentData, ComponentSparseSet, Group, GroupInfo,
-    GroupLayout, GroupMask, GroupMetadata, NonZeroStorageMask, QueryGroupInfo, QueryMask,
-    StorageMask, View, ViewMut,
+    group, ungroup_all, BorrowError, Component, ComponentData, ComponentSparseSet, Group,
+    GroupFamilyInfo, GroupInfo, GroupLayout, GroupMask, GroupMetadata, NonZeroStorageMask,
+    QueryGroupInfo, QueryMask, StorageMask, SubgroupInfo, View, ViewMut,
 };
-use crate::entity::Entity;
+use crate::entity::{Entity, EntityRemap, StorageMemory};
+use alloc::vec;
 use alloc::vec::Vec;
 use atomic_refcell::AtomicRefCell;
 use core::any::{self, TypeId};
@@ -20,6 +21,7 @@ pub(crate) struct ComponentStorage {
     pub(crate) groups: Vec<Group>,
     pub(crate) metadata: FxHashMap<TypeId, ComponentMetadata>,
     pub(crate) components: Vec<AtomicRefCell<ComponentSparseSet>>,
+    pub(crate) auto_register: bool,
 }
 
 impl ComponentStorage {
@@ -112,7 +114,15 @@ impl ComponentStorage {
     }
 
     pub fn register_dyn(&mut self, component: ComponentData) -> bool {
-        let Entry::Vacant(entry) = self.metadata.entry(component.type_id()) else {
+        self.register_with(component.type_id(), || component.create_sparse_set())
+    }
+
+    fn register_with(
+        &mut self,
+        type_id: TypeId,
+        create_sparse_set: impl FnOnce() -> ComponentSparseSet,
+    ) -> bool {
+        let Entry::Vacant(entry) = self.metadata.entry(type_id) else {
             return false;
         };
 
@@ -124,11 +134,113 @@ impl ComponentStorage {
         });
 
         self.components
-            .push(AtomicRefCell::new(component.create_sparse_set()));
+            .push(AtomicRefCell::new(create_sparse_set()));
 
         true
     }
 
+    pub fn merge(&mut self, other: &mut ComponentStorage, remap: &EntityRemap) {
+        for (&type_id, metadata) in &other.metadata {
+            let src = other.components[metadata.storage_index].get_mut();
+            self.register_with(type_id, || src.new_empty());
+
+            let dst = self.components[self.metadata[&type_id].storage_index].get_mut();
+
+            unsafe {
+                src.drain_into(dst, remap);
+            }
+        }
+
+        if !self.groups.is_empty() {
+            let group_mask = GroupMask::from_to(0, self.groups.len());
+
+            for (_, entity) in remap.iter() {
+                unsafe {
+                    group(&mut self.components, &mut self.groups, group_mask, entity);
+                }
+            }
+        }
+    }
+
+    pub fn insert<T>(&mut self, entity: Entity, component: T) -> Option<T>
+    where
+        T: Component,
+    {
+        let (storage_index, insert_mask) = self.insert_metadata::<T>();
+
+        unsafe {
+            let prev = self.components[storage_index]
+                .get_mut()
+                .insert(entity, component);
+
+            if prev.is_none() && insert_mask != GroupMask::EMPTY {
+                group(&mut self.components, &mut self.groups, insert_mask, entity);
+            }
+
+            prev
+        }
+    }
+
+    pub fn insert_many<T>(&mut self, components: impl IntoIterator<Item = (Entity, T)>)
+    where
+        T: Component,
+    {
+        let (storage_index, insert_mask) = self.insert_metadata::<T>();
+        let mut new_entities = Vec::new();
+
+        {
+            let sparse_set = self.components[storage_index].get_mut();
+
+            for (entity, component) in components {
+                let is_new = unsafe { sparse_set.insert(entity, component).is_none() };
+
+                if is_new && insert_mask != GroupMask::EMPTY {
+                    new_entities.push(entity);
+                }
+            }
+        }
+
+        // Group the new entities once all components are inserted. Unlike in
+        // `group_extend`, only one storage of each group grows, so the grouped
+        // ranges can't be extended in one step and each entity is moved into
+        // its groups individually.
+        for entity in new_entities {
+            unsafe {
+                group(&mut self.components, &mut self.groups, insert_mask, entity);
+            }
+        }
+    }
+
+    /// Returns the storage index and insert mask of `T`, registering `T` first
+    /// if auto-registration is enabled.
+    fn insert_metadata<T>(&mut self) -> (usize, GroupMask)
+    where
+        T: Component,
+    {
+        if self.auto_register {
+            self.register_dyn(ComponentData::new::<T>());
+        }
+
+        let Some(metadata) = self.metadata.get(&TypeId::of::<T>()) else {
+            panic_missing_comp::<T>();
+        };
+
+        (metadata.storage_index, metadata.insert_mask)
+    }
+
+    pub fn shrink_to_fit<T>(&mut self)
+    where
+        T: Component,
+    {
+        let Some(metadata) = self.metadata.get(&TypeId::of::<T>()) else {
+            panic_missing_comp::<T>();
+        };
+
+        self.components[metadata.storage_index]
+            .get_mut()
+            .shrink_to_fit();
+    }
+
     #[inline]
     #[must_use]
     pub fn is_registered_dyn(&self, type_id: TypeId) -> bool {
@@ -145,6 +257,53 @@ impl ComponentStorage {
         }
     }
 
+    #[must_use]
+    pub fn memory_usage(&self) -> Vec<StorageMemory> {
+        self.components
+            .iter()
+            .map(|sparse_set| sparse_set.borrow().memory_usage())
+            .collect()
+    }
+
+    #[must_use]
+    pub fn get_mut<T>(&mut self) -> Option<&mut ComponentSparseSet>
+    where
+        T: Component,
+    {
+        let metadata = self.metadata.get(&TypeId::of::<T>())?;
+        Some(self.components[metadata.storage_index].get_mut())
+    }
+
+    #[must_use]
+    pub fn group_families(&self) -> Vec<GroupFamilyInfo> {
+        let mut families = Vec::<GroupFamilyInfo>::new();
+        let mut prev_storage_start = None;
+
+        for group in &self.groups {
+            let metadata = &group.metadata;
+
+            let subgroup = SubgroupInfo {
+                components: self.components[metadata.storage_range()]
+                    .iter()
+                    .map(|sparse_set| sparse_set.borrow().type_name())
+                    .collect(),
+                len: group.len,
+            };
+
+            if prev_storage_start == Some(metadata.storage_start) {
+                families.last_mut().unwrap().groups.push(subgroup);
+            } else {
+                families.push(GroupFamilyInfo {
+                    groups: vec![subgroup],
+                });
+            }
+
+            prev_storage_start = Some(metadata.storage_start);
+        }
+
+        families
+    }
+
     pub fn clear(&mut self) {
         for group in &mut self.groups {
             group.len = 0;
@@ -156,7 +315,7 @@ impl ComponentStorage {
     }
 
     #[must_use]
-    pub fn borrow<T>(&self) -> View<T>
+    pub fn borrow<T>(&self) -> View<'_, T>
     where
         T: Component,
     {
@@ -168,13 +327,14 @@ impl ComponentStorage {
             View::new(
                 self.components
                     .get_unchecked(metadata.storage_index)
-                    .borrow(),
+                    .try_borrow()
+                    .unwrap_or_else(|_| panic_borrowed_comp_mut::<T>()),
             )
         }
     }
 
     #[must_use]
-    pub fn borrow_mut<T>(&self) -> ViewMut<T>
+    pub fn borrow_mut<T>(&self) -> ViewMut<'_, T>
     where
         T: Component,
     {
@@ -186,13 +346,51 @@ impl ComponentStorage {
             ViewMut::new(
                 self.components
                     .get_unchecked(metadata.storage_index)
-                    .borrow_mut(),
+                    .try_borrow_mut()
+                    .unwrap_or_else(|_| panic_borrowed_comp::<T>()),
+                metadata.group_info.is_some(),
             )
         }
     }
 
+    pub fn try_borrow<T>(&self) -> Result<View<'_, T>, BorrowError>
+    where
+        T: Component,
+    {
+        let metadata = self
+            .metadata
+            .get(&TypeId::of::<T>())
+            .ok_or(BorrowError::Unregistered)?;
+
+        unsafe {
+            self.components
+                .get_unchecked(metadata.storage_index)
+                .try_borrow()
+                .map(View::new)
+                .map_err(|_| BorrowError::AlreadyBorrowed)
+        }
+    }
+
+    pub fn try_borrow_mut<T>(&self) -> Result<ViewMut<'_, T>, BorrowError>
+    where
+        T: Component,
+    {
+        let metadata = self
+            .metadata
+            .get(&TypeId::of::<T>())
+            .ok_or(BorrowError::Unregistered)?;
+
+        unsafe {
+            self.components
+                .get_unchecked(metadata.storage_index)
+                .try_borrow_mut()
+                .map(|components| ViewMut::new(components, metadata.group_info.is_some()))
+                .map_err(|_| BorrowError::AlreadyBorrowed)
+        }
+    }
+
     #[must_use]
-    pub fn borrow_with_group_info<T>(&self) -> (View<T>, Option<GroupInfo>)
+    pub fn borrow_with_group_info<T>(&self) -> (View<'_, T>, Option<GroupInfo>)
     where
         T: Component,
     {
@@ -204,7 +402,8 @@ impl ComponentStorage {
             View::new(
                 self.components
                     .get_unchecked(metadata.storage_index)
-                    .borrow(),
+                    .try_borrow()
+                    .unwrap_or_else(|_| panic_borrowed_comp_mut::<T>()),
             )
         };
 
@@ -212,7 +411,7 @@ impl ComponentStorage {
     }
 
     #[must_use]
-    pub fn borrow_with_group_info_mut<T>(&self) -> (ViewMut<T>, Option<GroupInfo>)
+    pub fn borrow_with_group_info_mut<T>(&self) -> (ViewMut<'_, T>, Option<GroupInfo>)
     where
         T: Component,
     {
@@ -224,7 +423,9 @@ impl ComponentStorage {
             ViewMut::new(
                 self.components
                     .get_unchecked(metadata.storage_index)
-                    .borrow_mut(),
+                    .try_borrow_mut()
+                    .unwrap_or_else(|_| panic_borrowed_comp::<T>()),
+                metadata.group_info.is_some(),
             )
         };
 
@@ -326,3 +527,18 @@ pub(crate) struct ComponentMetadata {
 pub(crate) fn panic_missing_comp<T>() -> ! {
     panic!("Component '{}' was not registered", any::type_name::<T>());
 }
+
+#[cold]
+#[inline(never)]
+fn panic_borrowed_comp<T>() -> ! {
+    panic!("Component '{}' is already borrowed", any::type_name::<T>());
+}
+
+#[cold]
+#[inline(never)]
+fn panic_borrowed_comp_mut<T>() -> ! {
+    panic!(
+        "Component '{}' is already borrowed mutably",
+        any::type_name::<T>()
+    );
+}
@@ -81,6 +81,45 @@ pub(crate) unsafe fn group(
     }
 }
 
+/// Groups `entities`, which must have just been appended to the storages with
+/// the same set of components.
+///
+/// Groups whose storages held only grouped entities before the append are
+/// extended in one step, as the new entities already sit right after the
+/// grouped range in every storage. The remaining groups are updated one entity
+/// at a time.
+pub(crate) unsafe fn group_extend(
+    components: &mut [AtomicRefCell<ComponentSparseSet>],
+    groups: &mut [Group],
+    mut group_mask: GroupMask,
+    entities: &[Entity],
+) {
+    let Some(&first_entity) = entities.first() else {
+        return;
+    };
+
+    for group_index in group_mask.iter_bit_indexes() {
+        let group = groups.get_unchecked_mut(group_index as usize);
+        let new_len = group.len + entities.len();
+
+        let is_appended = components[group.metadata.storage_range()]
+            .iter_mut()
+            .map(AtomicRefCell::get_mut)
+            .all(|sparse_set| sparse_set.len() == new_len && sparse_set.contains(first_entity));
+
+        if is_appended {
+            group.len = new_len;
+            group_mask.0 &= !(1 << group_index);
+        }
+    }
+
+    if group_mask != GroupMask::EMPTY {
+        for &entity in entities {
+            group(components, groups, group_mask, entity);
+        }
+    }
+}
+
 pub(crate) unsafe fn ungroup(
     components: &mut [AtomicRefCell<ComponentSparseSet>],
     groups: &mut [Group],
@@ -0,0 +1,19 @@
+use alloc::vec::Vec;
+
+/// Read-only description of a group family in a [`World`](crate::World).
+///
+/// A family is made of groups whose components are supersets of each other.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct GroupFamilyInfo {
+    /// The groups in the family, from the smallest to the largest.
+    pub groups: Vec<SubgroupInfo>,
+}
+
+/// Read-only description of a group in a group family.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SubgroupInfo {
+    /// Names of the components in the group, in storage order.
+    pub components: Vec<&'static str>,
+    /// Number of entities currently grouped.
+    pub len: usize,
+}
@@ -80,7 +80,7 @@ pub struct ViewGroupInfo {
 }
 
 /// Grouping information for one or more views.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct GroupInfo {
     pub(crate) group_start: u8,
     pub(crate) group_end: u8,
@@ -94,7 +94,7 @@ impl GroupInfo {
     #[inline]
     #[must_use]
     pub fn add_group(self, other: GroupInfo) -> Option<GroupInfo> {
-        if self.group_start != other.group_start {
+        if !self.same_group_family(&other) {
             return None;
         }
 
@@ -104,4 +104,12 @@ impl GroupInfo {
             storage_mask: self.storage_mask | other.storage_mask,
         })
     }
+
+    /// Returns whether both group infos belong to the same group family, i.e.
+    /// whether their components are grouped together.
+    #[inline]
+    #[must_use]
+    pub fn same_group_family(&self, other: &GroupInfo) -> bool {
+        self.group_start == other.group_start
+    }
 }
@@ -15,6 +15,9 @@ pub const MAX_GROUP_COUNT: usize = 64;
 
 /// Describes the layout of the component groups that can be set on a
 /// [`World`](crate::world::World).
+///
+/// A layout can hold at most [`MAX_GROUP_COUNT`] groups, each made of at most
+/// [`MAX_GROUP_ARITY`] component types.
 #[derive(Clone, Default, Debug)]
 pub struct GroupLayout {
     families: Vec<GroupFamily>,
@@ -58,6 +61,17 @@ impl GroupLayout {
                 "Groups families may not have any component types in common",
             );
         }
+
+        assert!(
+            self.group_count() <= MAX_GROUP_COUNT,
+            "Group layouts must have at most {MAX_GROUP_COUNT} groups",
+        );
+    }
+
+    /// Returns the number of groups in the layout.
+    #[must_use]
+    pub fn group_count(&self) -> usize {
+        self.families.iter().map(|f| f.arities().len()).sum()
     }
 
     /// Returns the group families of this layout.
@@ -11,21 +11,19 @@ impl GroupMask {
     #[inline]
     #[must_use]
     pub const fn from_to(from: usize, to: usize) -> Self {
-        assert!(from < MAX_GROUP_COUNT);
-        assert!(to < MAX_GROUP_COUNT);
         assert!(from <= to);
+        assert!(to <= MAX_GROUP_COUNT);
 
-        Self(((1 << (to - from)) - 1) << from)
+        match to - from {
+            0 => Self::EMPTY,
+            len => Self((u64::MAX >> (u64::BITS as usize - len)) << from),
+        }
     }
 
     #[inline]
     #[must_use]
     pub const fn skip_from_to(from: usize, to: usize) -> Self {
-        assert!(from < MAX_GROUP_COUNT);
-        assert!(to < MAX_GROUP_COUNT);
-        assert!(from <= to);
-
-        Self(!(((1 << (to - from)) - 1) << from))
+        Self(!Self::from_to(from, to).0)
     }
 
     #[inline]
@@ -125,7 +123,7 @@ impl DoubleEndedIterator for BitIndexIter {
             return None;
         }
 
-        let index = 63 - self.0.leading_zeros();
+        let index = self.0.ilog2();
         self.0 &= !(1 << index);
         Some(index)
     }
@@ -1,18 +1,22 @@
 //! Component storage and management.
 
+mod borrow_error;
 mod component_data;
 mod component_set;
 mod component_sparse_set;
 mod component_storage;
 mod group;
+mod group_family_info;
 mod group_info;
 mod group_layout;
 mod group_mask;
 mod storage_mask;
 mod view;
 
+pub use self::borrow_error::*;
 pub use self::component_data::*;
 pub use self::component_set::*;
+pub use self::group_family_info::*;
 pub use self::group_info::*;
 pub use self::group_layout::*;
 pub use self::view::*;
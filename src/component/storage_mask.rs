@@ -10,11 +10,13 @@ impl StorageMask {
     #[inline]
     #[must_use]
     pub fn from_to(from: usize, to: usize) -> Self {
-        assert!(from < MAX_GROUP_ARITY);
-        assert!(to < MAX_GROUP_ARITY);
         assert!(from <= to);
+        assert!(to <= MAX_GROUP_ARITY);
 
-        Self(((1 << (to - from)) - 1) << from)
+        match to - from {
+            0 => Self::EMPTY,
+            len => Self((u16::MAX >> (u16::BITS as usize - len)) << from),
+        }
     }
 }
 
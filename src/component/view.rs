@@ -1,10 +1,14 @@
 use crate::component::{Component, ComponentSparseSet};
 use crate::entity::{Entity, SparseVec};
+use alloc::vec::Vec;
 use atomic_refcell::{AtomicRef, AtomicRefMut};
-use core::fmt;
 use core::marker::PhantomData;
 use core::ops::{Index, IndexMut};
 use core::ptr::NonNull;
+use core::{any, fmt};
+
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelRefIterator;
 
 /// Shared view over all components of type `T` in a
 /// [`World`](crate::world::World).
@@ -28,6 +32,7 @@ impl<'a, T> View<'a, T> {
 /// [`World`](crate::world::World).
 pub struct ViewMut<'a, T> {
     components: AtomicRefMut<'a, ComponentSparseSet>,
+    grouped: bool,
     _phantom: PhantomData<&'a mut [T]>,
 }
 
@@ -37,9 +42,10 @@ where
 {
     #[inline]
     #[must_use]
-    pub(crate) fn new(components: AtomicRefMut<'a, ComponentSparseSet>) -> Self {
+    pub(crate) fn new(components: AtomicRefMut<'a, ComponentSparseSet>, grouped: bool) -> Self {
         Self {
             components,
+            grouped,
             _phantom: PhantomData,
         }
     }
@@ -51,11 +57,68 @@ where
         unsafe { self.components.get_mut::<T>(entity) }
     }
 
-    /// Returns a mutable slice of all components in the view.
+    /// Returns a mutable slice of all components in the view, in the same
+    /// order as [`entities`](Self::entities).
     #[must_use]
     pub fn as_mut_slice(&mut self) -> &mut [T] {
         unsafe { self.components.as_mut_slice::<T>() }
     }
+
+    /// Returns a slice of all entities and a mutable slice of all components in
+    /// the view.
+    #[must_use]
+    pub fn split_mut(&mut self) -> (&[Entity], &mut [T]) {
+        unsafe { self.components.split_mut::<T>() }
+    }
+
+    /// Reorders the entities and components in the view by the key `f`
+    /// returns for each of them. Useful for improving cache locality when
+    /// iterating the view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component is part of a group, because grouped storages
+    /// must keep their grouped entities first.
+    pub fn sort_dense_by<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(Entity, &T) -> K,
+    {
+        assert!(
+            !self.grouped,
+            "Cannot sort grouped component '{}'",
+            any::type_name::<T>(),
+        );
+
+        let mut order = (0..self.components.len()).collect::<Vec<_>>();
+
+        {
+            let entities = self.components.entities();
+            let components = unsafe { self.components.as_slice::<T>() };
+            order.sort_by_cached_key(|&i| f(entities[i], &components[i]));
+        }
+
+        // Dense position of each original item, and original item at each
+        // dense position.
+        let mut positions = (0..order.len()).collect::<Vec<_>>();
+        let mut items = positions.clone();
+
+        for (dense, &item) in order.iter().enumerate() {
+            let position = positions[item];
+
+            if position != dense {
+                unsafe {
+                    self.components.swap(dense, position);
+                }
+
+                let swapped_item = items[dense];
+                items[position] = swapped_item;
+                positions[swapped_item] = position;
+                items[dense] = item;
+                positions[item] = dense;
+            }
+        }
+    }
 }
 
 impl<T> IndexMut<Entity> for ViewMut<'_, T>
@@ -85,6 +148,19 @@ macro_rules! impl_view_common {
                 self.components.contains(entity)
             }
 
+            /// Returns the position of `entity` in the view, if it exists.
+            ///
+            /// The position indexes the slices returned by
+            /// [`entities`](Self::entities) and [`as_slice`](Self::as_slice),
+            /// and is found in constant time.
+            #[must_use]
+            pub fn dense_index(&self, entity: Entity) -> Option<usize> {
+                self.components
+                    .sparse()
+                    .get(entity)
+                    .map(|index| index as usize)
+            }
+
             /// Returns the number of entities in the view.
             #[must_use]
             pub fn len(&self) -> usize {
@@ -103,12 +179,25 @@ macro_rules! impl_view_common {
                 self.components.entities()
             }
 
-            /// Returns a slice of all components in the view.
+            /// Returns a slice of all components in the view, in the same order
+            /// as [`entities`](Self::entities).
             #[must_use]
             pub fn as_slice(&self) -> &[T] {
                 unsafe { self.components.as_slice::<T>() }
             }
 
+            /// Returns a parallel iterator over all entities in the view.
+            #[cfg(feature = "parallel")]
+            pub fn par_entities(&self) -> rayon::slice::Iter<'_, Entity> {
+                self.entities().par_iter()
+            }
+
+            /// Returns a parallel iterator over all components in the view.
+            #[cfg(feature = "parallel")]
+            pub fn par_components(&self) -> rayon::slice::Iter<'_, T> {
+                self.as_slice().par_iter()
+            }
+
             #[must_use]
             pub(crate) fn sparse(&self) -> &SparseVec {
                 self.components.sparse()
@@ -120,6 +209,37 @@ macro_rules! impl_view_common {
             }
         }
 
+        impl<T> $View<'_, T>
+        where
+            T: Component + Clone,
+        {
+            /// Returns all entities in the view paired with clones of their
+            /// components.
+            #[must_use]
+            pub fn to_vec(&self) -> Vec<(Entity, T)> {
+                self.entities()
+                    .iter()
+                    .copied()
+                    .zip(self.as_slice().iter().cloned())
+                    .collect()
+            }
+        }
+
+        impl<T> $View<'_, T>
+        where
+            T: Component + PartialEq,
+        {
+            /// Returns whether every entity in `expected` is present in the
+            /// view with a component equal to the expected one, regardless of
+            /// the order of the view.
+            #[must_use]
+            pub fn contains_all(&self, expected: &[(Entity, T)]) -> bool {
+                expected
+                    .iter()
+                    .all(|(entity, component)| self.get(*entity) == Some(component))
+            }
+        }
+
         impl<T> Index<Entity> for $View<'_, T>
         where
             T: Component,
@@ -1,6 +1,52 @@
 use crate::entity::Entity;
 use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::ops::Range;
 use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use core::{error, fmt};
+use hashbrown::HashMap;
+use rustc_hash::FxBuildHasher;
+
+/// Statistics about the entities allocated by a
+/// [`World`](crate::world::World).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct AllocatorStats {
+    /// The number of entities currently in the world.
+    pub live: usize,
+    /// The number of destroyed entities waiting to be reused.
+    pub recycled: usize,
+    /// The number of distinct entity indexes allocated so far. Indexes skipped
+    /// by [`World::create_at`](crate::world::World::create_at) are only
+    /// counted once they are allocated.
+    pub high_water_mark: u64,
+}
+
+/// Error returned by [`World::create_at`](crate::world::World::create_at).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CreateAtError {
+    /// An entity with the same index is already in the world.
+    Exists(Entity),
+    /// The version is older than the version the index would be reused with.
+    /// Contains the oldest entity that can be created at that index.
+    StaleVersion(Entity),
+    /// The index can't be reused, either because it ran out of versions or
+    /// because its entity was removed by [`World::clear`](crate::world::World::clear).
+    Retired,
+}
+
+impl fmt::Display for CreateAtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exists(entity) => write!(f, "Entity {entity} is already in the world"),
+            Self::StaleVersion(entity) => {
+                write!(f, "Entity version is older than the version of {entity}")
+            }
+            Self::Retired => f.write_str("Entity index can't be reused"),
+        }
+    }
+}
+
+impl error::Error for CreateAtError {}
 
 #[derive(Default, Debug)]
 pub(crate) struct EntityAllocator {
@@ -8,41 +54,144 @@ pub(crate) struct EntityAllocator {
     last_maintained_index: u64,
     recycled: VecDeque<Entity>,
     recycled_since_maintain: AtomicUsize,
+    // Maps the indexes in `recycled` to the order in which they were added, so
+    // their position can be found in constant time.
+    recycled_order: HashMap<u32, u64, FxBuildHasher>,
+    recycled_count: u64,
+    // Sorted, disjoint ranges of indexes skipped by `allocate_at`.
+    skipped: Vec<Range<u32>>,
 }
 
 impl EntityAllocator {
     #[must_use]
     pub fn allocate(&mut self) -> Option<Entity> {
-        let recycled_since_maintain = *self.recycled_since_maintain.get_mut();
+        debug_assert!(!self.has_pending());
+
+        if let Some(entity) = self.recycled.pop_back() {
+            self.recycled_order.remove(&entity.index);
+            Some(entity)
+        } else if let Some(range) = self.skipped.last_mut() {
+            let index = range.start;
+            range.start += 1;
+
+            if range.start == range.end {
+                self.skipped.pop();
+            }
 
-        if recycled_since_maintain < self.recycled.len() {
-            *self.recycled_since_maintain.get_mut() += 1;
-            Some(self.recycled[self.recycled.len() - recycled_since_maintain - 1])
+            Some(Entity::with_index(index))
         } else if let Ok(index) = u32::try_from(*self.next_index_to_allocate.get_mut()) {
             *self.next_index_to_allocate.get_mut() += 1;
+            self.last_maintained_index += 1;
             Some(Entity::with_index(index))
         } else {
             None
         }
     }
 
+    /// Allocates the given `entity`. The caller must ensure no entity with the
+    /// same index is alive.
+    pub fn allocate_at(&mut self, entity: Entity) -> Result<(), CreateAtError> {
+        debug_assert!(!self.has_pending());
+
+        let next_index_to_allocate = *self.next_index_to_allocate.get_mut();
+        let index = u64::from(entity.index);
+
+        if index >= next_index_to_allocate {
+            // Skipped indexes are stored as a range and allocated lazily.
+            if index > next_index_to_allocate {
+                self.skipped
+                    .push(next_index_to_allocate as u32..entity.index);
+            }
+
+            *self.next_index_to_allocate.get_mut() = index + 1;
+            self.last_maintained_index = index + 1;
+            return Ok(());
+        }
+
+        if let Some(&order) = self.recycled_order.get(&entity.index) {
+            let position = (self.recycled_count - 1 - order) as usize;
+            let recycled = self.recycled[position];
+
+            if entity.version < recycled.version {
+                return Err(CreateAtError::StaleVersion(recycled));
+            }
+
+            self.recycled_order.remove(&entity.index);
+            self.recycled.swap_remove_back(position);
+
+            if let Some(moved) = self.recycled.get(position) {
+                self.recycled_order.insert(moved.index, order);
+            }
+
+            return Ok(());
+        }
+
+        let i = self
+            .skipped
+            .partition_point(|range| range.end <= entity.index);
+
+        match self.skipped.get(i) {
+            Some(range) if range.contains(&entity.index) => {
+                let after = (entity.index + 1)..range.end;
+                self.skipped[i].end = entity.index;
+
+                if !after.is_empty() {
+                    self.skipped.insert(i + 1, after);
+                }
+
+                if self.skipped[i].is_empty() {
+                    self.skipped.remove(i);
+                }
+
+                Ok(())
+            }
+            _ => Err(CreateAtError::Retired),
+        }
+    }
+
     #[must_use]
     pub fn allocate_atomic(&self) -> Option<Entity> {
         match self.increment_recycled_since_maintain() {
             Some(recycled_since_maintain) => {
                 Some(self.recycled[self.recycled.len() - recycled_since_maintain - 1])
             }
-            None => {
-                self.increment_next_index_to_allocate()
-                    .map(Entity::with_index)
-            }
+            None => self
+                .increment_next_index_to_allocate()
+                .map(Entity::with_index),
         }
     }
 
+    #[must_use]
+    pub fn has_pending(&mut self) -> bool {
+        *self.recycled_since_maintain.get_mut() != 0
+            || *self.next_index_to_allocate.get_mut() != self.last_maintained_index
+    }
+
+    #[must_use]
+    pub fn recycled_len(&self) -> usize {
+        self.recycled.len() - self.recycled_since_maintain.load(Ordering::Relaxed)
+    }
+
+    #[must_use]
+    pub fn allocated_len(&self) -> u64 {
+        let skipped = self
+            .skipped
+            .iter()
+            .map(|range| u64::from(range.end - range.start))
+            .sum::<u64>();
+
+        self.next_index_to_allocate.load(Ordering::Relaxed) - skipped
+    }
+
     pub fn recycle(&mut self, entity: Entity) {
         if let Some(next_version) = entity.version.next() {
             self.recycled
                 .push_front(Entity::new(entity.index, next_version));
+
+            self.recycled_order
+                .insert(entity.index, self.recycled_count);
+
+            self.recycled_count += 1;
         }
     }
 
@@ -53,6 +202,10 @@ impl EntityAllocator {
             (self.recycled.len() - recycled_since_maintain)..
         };
 
+        for entity in self.recycled.range(recycled_range.clone()) {
+            self.recycled_order.remove(&entity.index);
+        }
+
         let new_index_range = {
             let next_index_to_allocate = *self.next_index_to_allocate.get_mut();
             let new_index_range = self.last_maintained_index..next_index_to_allocate;
@@ -70,6 +223,9 @@ impl EntityAllocator {
         self.last_maintained_index = 0;
         self.recycled.clear();
         *self.recycled_since_maintain.get_mut() = 0;
+        self.recycled_order.clear();
+        self.recycled_count = 0;
+        self.skipped.clear();
     }
 
     fn increment_recycled_since_maintain(&self) -> Option<usize> {
@@ -0,0 +1,48 @@
+use crate::entity::Entity;
+use hashbrown::HashMap;
+use rustc_hash::FxBuildHasher;
+
+/// Maps entities from one [`World`](crate::world::World) to the entities that
+/// replaced them in another.
+#[derive(Clone, Default, Debug)]
+pub struct EntityRemap {
+    entities: HashMap<Entity, Entity, FxBuildHasher>,
+}
+
+impl EntityRemap {
+    /// Returns the entity that replaced `entity`, if any.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, entity: Entity) -> Option<Entity> {
+        self.entities.get(&entity).copied()
+    }
+
+    /// Returns the number of remapped entities.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns whether no entities were remapped.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns an iterator over all pairs of old and new entities.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.entities.iter().map(|(&old, &new)| (old, new))
+    }
+
+    #[inline]
+    pub(crate) fn insert(&mut self, old: Entity, new: Entity) {
+        self.entities.insert(old, new);
+    }
+
+    #[inline]
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.entities.reserve(additional);
+    }
+}
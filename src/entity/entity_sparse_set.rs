@@ -1,6 +1,6 @@
-use crate::entity::{Entity, SparseVec, SparseVecSlot};
+use crate::entity::{Entity, SparseVec, SparseVecSlot, StorageMemory};
 use alloc::vec::Vec;
-use core::{fmt, mem};
+use core::{any, fmt, mem};
 
 #[derive(Clone, Default)]
 pub(crate) struct EntitySparseSet {
@@ -12,23 +12,22 @@ impl EntitySparseSet {
     pub fn insert(&mut self, entity: Entity) -> Option<Entity> {
         let dense_entity = self.sparse.get_mut_or_allocate_at(entity.sparse());
 
-        match dense_entity {
-            Some(dense_entity) => unsafe {
+        if let Some(dense_entity) = dense_entity {
+            return unsafe {
                 Some(mem::replace(
                     self.entities.get_unchecked_mut(dense_entity.index as usize),
                     entity,
                 ))
-            },
-            None => {
-                *dense_entity = Some(SparseVecSlot {
-                    index: self.entities.len() as u32,
-                    version: entity.version,
-                });
-
-                self.entities.push(entity);
-                None
-            }
+            };
         }
+
+        *dense_entity = Some(SparseVecSlot {
+            index: self.entities.len() as u32,
+            version: entity.version,
+        });
+
+        self.entities.push(entity);
+        None
     }
 
     pub fn remove(&mut self, entity: Entity) -> bool {
@@ -51,6 +50,18 @@ impl EntitySparseSet {
         true
     }
 
+    pub fn reserve(&mut self, additional: usize) {
+        self.sparse.reserve(additional);
+        self.entities.reserve(additional);
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn get_by_index(&self, index: u32) -> Option<Entity> {
+        let dense = self.sparse.get_sparse(index as usize)?;
+        Some(self.entities[dense as usize])
+    }
+
     #[inline]
     #[must_use]
     pub fn contains(&self, entity: Entity) -> bool {
@@ -75,6 +86,21 @@ impl EntitySparseSet {
         &self.entities
     }
 
+    #[must_use]
+    pub fn memory_usage(&self) -> StorageMemory {
+        let slot_size = mem::size_of::<Option<SparseVecSlot>>();
+        let entity_size = mem::size_of::<Entity>();
+
+        StorageMemory {
+            type_name: any::type_name::<Entity>(),
+            len: self.entities.len(),
+            capacity: self.entities.capacity(),
+            used_bytes: self.sparse.len() * slot_size + self.entities.len() * entity_size,
+            allocated_bytes: self.sparse.capacity() * slot_size
+                + self.entities.capacity() * entity_size,
+        }
+    }
+
     #[inline]
     pub fn clear(&mut self) {
         self.sparse.clear();
@@ -1,14 +1,32 @@
-use crate::entity::{Entity, EntityAllocator, EntitySparseSet};
+use crate::entity::{
+    AllocatorStats, CreateAtError, Entity, EntityAllocator, EntitySparseSet, StorageMemory,
+};
 
 #[derive(Default, Debug)]
 pub(crate) struct EntityStorage {
     allocator: EntityAllocator,
     entities: EntitySparseSet,
+    deterministic: bool,
 }
 
 impl EntityStorage {
+    #[must_use]
+    pub fn deterministic() -> Self {
+        Self {
+            deterministic: true,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
     #[must_use]
     pub fn create(&mut self) -> Entity {
+        self.maintain();
+
         let entity = self
             .allocator
             .allocate()
@@ -18,24 +36,63 @@ impl EntityStorage {
         entity
     }
 
+    pub fn create_at(&mut self, entity: Entity) -> Result<(), CreateAtError> {
+        self.maintain();
+
+        if let Some(existing) = self.entities.get_by_index(entity.index) {
+            return Err(CreateAtError::Exists(existing));
+        }
+
+        self.allocator.allocate_at(entity)?;
+        self.entities.insert(entity);
+        Ok(())
+    }
+
     #[must_use]
     pub fn create_atomic(&self) -> Entity {
+        assert!(
+            !self.deterministic,
+            "Atomic entity creation is disabled in deterministic worlds",
+        );
+
         self.allocator
             .allocate_atomic()
             .expect("No entities left to allocate")
     }
 
     pub fn maintain(&mut self) {
+        if !self.allocator.has_pending() {
+            return;
+        }
+
         self.allocator.maintain().for_each(|entity| {
             self.entities.insert(entity);
         });
     }
 
+    pub fn reserve(&mut self, additional: usize) {
+        self.entities.reserve(additional);
+    }
+
     #[must_use]
     pub fn contains(&self, entity: Entity) -> bool {
         self.entities.contains(entity)
     }
 
+    #[must_use]
+    pub fn memory_usage(&self) -> StorageMemory {
+        self.entities.memory_usage()
+    }
+
+    #[must_use]
+    pub fn allocator_stats(&self) -> AllocatorStats {
+        AllocatorStats {
+            live: self.entities.len(),
+            recycled: self.allocator.recycled_len(),
+            high_water_mark: self.allocator.allocated_len(),
+        }
+    }
+
     #[must_use]
     pub fn len(&self) -> usize {
         self.entities.len()
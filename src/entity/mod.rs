@@ -1,19 +1,28 @@
 //! Entity storage and allocation.
 
 mod entity_allocator;
+mod entity_remap;
 mod entity_sparse_set;
 mod entity_storage;
 mod sparse_vec;
+mod storage_memory;
 
+pub use self::entity_allocator::{AllocatorStats, CreateAtError};
+pub use self::entity_remap::*;
 pub use self::sparse_vec::*;
+pub use self::storage_memory::*;
 
 pub(crate) use self::entity_allocator::*;
 pub(crate) use self::entity_sparse_set::*;
 pub(crate) use self::entity_storage::*;
 
 use core::cmp::Ordering;
-use core::fmt;
 use core::num::NonZeroU32;
+use core::{fmt, mem};
+
+// Versions are non-zero, so optional entities and slots take no extra space.
+const _: () = assert!(mem::size_of::<Option<Entity>>() == mem::size_of::<Entity>());
+const _: () = assert!(mem::size_of::<Option<SparseVecSlot>>() == mem::size_of::<SparseVecSlot>());
 
 /// Uniquely identifies a set of components in a
 /// [`World`](crate::world::World).
@@ -79,6 +88,12 @@ impl fmt::Debug for Entity {
     }
 }
 
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.version.0)
+    }
+}
+
 /// Version used to distinguish between entities with the same index.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct Version(pub NonZeroU32);
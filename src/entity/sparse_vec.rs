@@ -16,6 +16,34 @@ impl SparseVec {
         Self { slots: Vec::new() }
     }
 
+    /// Reserves capacity for at least `additional` more sparse indexes.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.slots.reserve(additional);
+    }
+
+    /// Returns the number of sparse indexes the sparse vec can map.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns whether the sparse vec can't map any sparse indexes.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Returns the number of sparse indexes the sparse vec can map without
+    /// reallocating.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
     /// Returns the dense index mapped to `entity`, if any.
     #[inline]
     #[must_use]
@@ -59,6 +87,11 @@ impl SparseVec {
     #[inline]
     #[must_use]
     pub unsafe fn get_sparse_unchecked(&self, sparse: usize) -> usize {
+        debug_assert!(
+            self.contains_sparse(sparse),
+            "Sparse index {sparse} is not present in the sparse vec",
+        );
+
         self.slots.get_unchecked(sparse).unwrap_unchecked().dense()
     }
 
@@ -81,6 +114,11 @@ impl SparseVec {
     /// is valid.
     #[inline]
     pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut Option<SparseVecSlot> {
+        debug_assert!(
+            index < self.slots.len(),
+            "Sparse index {index} is out of bounds",
+        );
+
         self.slots.get_unchecked_mut(index)
     }
 
@@ -107,6 +145,27 @@ impl SparseVec {
         mem::swap(entity_a, entity_b);
     }
 
+    /// Removes the trailing empty slots and shrinks the capacity of the sparse
+    /// vec as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        let len = self
+            .slots
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |i| i + 1);
+
+        self.slots.truncate(len);
+        self.slots.shrink_to_fit();
+    }
+
+    /// Returns an iterator over all occupied slots and their sparse indexes.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, SparseVecSlot)> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(sparse, slot)| Some((sparse, (*slot)?)))
+    }
+
     /// Removes all entities from the storage.
     #[inline]
     pub fn clear(&mut self) {
@@ -116,21 +175,16 @@ impl SparseVec {
     #[cold]
     fn extend_to_index(&mut self, index: usize) {
         let extra_len = index.checked_next_power_of_two().unwrap_or(index) - self.slots.len() + 1;
-        self.slots.extend(iter::repeat(None).take(extra_len));
+        self.slots.extend(iter::repeat_n(None, extra_len));
     }
 }
 
 impl fmt::Debug for SparseVec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let entries = self
-            .slots
-            .iter()
-            .enumerate()
-            .filter_map(|(i, &dense_entity)| {
-                let dense_entity = dense_entity?;
-                let entity = Entity::new(i as u32, dense_entity.version);
-                Some((entity, dense_entity.index))
-            });
+        let entries = self.iter().map(|(sparse, slot)| {
+            let entity = Entity::new(sparse as u32, slot.version);
+            (entity, slot.index)
+        });
 
         f.debug_map().entries(entries).finish()
     }
@@ -0,0 +1,15 @@
+/// Memory used by a single entity or component storage.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct StorageMemory {
+    /// Name of the stored type.
+    pub type_name: &'static str,
+    /// Number of items in the storage.
+    pub len: usize,
+    /// Number of items the storage can hold without reallocating.
+    pub capacity: usize,
+    /// Number of bytes occupied by the items in the storage, including the
+    /// sparse index.
+    pub used_bytes: usize,
+    /// Number of bytes allocated by the storage, including the sparse index.
+    pub allocated_bytes: usize,
+}
@@ -4,7 +4,7 @@
 //! # Example
 //!
 //! ```rust
-//! use sparsey::World;
+//! use sparsey::prelude::*;
 //!
 //! struct Position(i32, i32);
 //! struct Velocity(i32, i32);
@@ -44,6 +44,7 @@ extern crate alloc;
 
 pub mod component;
 pub mod entity;
+pub mod prelude;
 pub mod query;
 pub mod world;
 
@@ -0,0 +1,6 @@
+//! Re-exports the most commonly used types.
+
+pub use crate::component::{Component, GroupLayout, View, ViewMut};
+pub use crate::entity::Entity;
+pub use crate::query::{QueryAll, QueryOne};
+pub use crate::world::{World, WorldBuilder};
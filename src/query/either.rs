@@ -0,0 +1,77 @@
+use crate::query::Query;
+use crate::World;
+
+/// Item returned by queries over the union of two queries.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Either<L, R> {
+    /// The entity only matches the left query.
+    Left(L),
+    /// The entity only matches the right query.
+    Right(R),
+    /// The entity matches both queries.
+    Both(L, R),
+}
+
+impl<L, R> Either<L, R> {
+    /// Returns the left item, if any.
+    #[inline]
+    #[must_use]
+    pub fn left(self) -> Option<L> {
+        match self {
+            Self::Left(left) | Self::Both(left, _) => Some(left),
+            Self::Right(_) => None,
+        }
+    }
+
+    /// Returns the right item, if any.
+    #[inline]
+    #[must_use]
+    pub fn right(self) -> Option<R> {
+        match self {
+            Self::Right(right) | Self::Both(_, right) => Some(right),
+            Self::Left(_) => None,
+        }
+    }
+}
+
+pub(crate) fn for_each_either<L, R, F>(world: &World, mut f: F)
+where
+    L: Query,
+    R: Query,
+    F: FnMut(Either<L::Item<'_>, R::Item<'_>>),
+{
+    let left = L::borrow(world);
+    let right = R::borrow(world);
+
+    let (left_entities, left_sparse, left_data) = L::split_sparse_parts(&left);
+    let (right_entities, right_sparse, right_data) = R::split_sparse_parts(&right);
+
+    let left_entities = left_entities.unwrap_or(world.entities());
+    let right_entities = right_entities.unwrap_or(world.entities());
+
+    for &entity in left_entities {
+        unsafe {
+            let Some(left_item) = L::get_sparse_raw(left_sparse, left_data, entity) else {
+                continue;
+            };
+
+            match R::get_sparse_raw(right_sparse, right_data, entity) {
+                Some(right_item) => f(Either::Both(left_item, right_item)),
+                None => f(Either::Left(left_item)),
+            }
+        }
+    }
+
+    for &entity in right_entities {
+        // Entities matching the left query were already visited.
+        if L::contains_all_raw(left_sparse, entity.sparse()) {
+            continue;
+        }
+
+        unsafe {
+            if let Some(right_item) = R::get_sparse_raw(right_sparse, right_data, entity) {
+                f(Either::Right(right_item));
+            }
+        }
+    }
+}
@@ -32,6 +32,15 @@ where
             get_data,
         }
     }
+
+    /// Returns an iterator over the remaining items and their dense indexes.
+    ///
+    /// The dense index of an item is its position in the slices returned by
+    /// [`View::as_slice`](crate::component::View::as_slice) for all the
+    /// queried components.
+    pub fn enumerate_dense(self) -> impl Iterator<Item = (usize, G::Item<'a>)> {
+        self.range.clone().zip(self)
+    }
 }
 
 impl<'a, G> Iterator for DenseIter<'a, G>
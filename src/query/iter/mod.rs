@@ -40,6 +40,27 @@ where
     }
 }
 
+impl<'a, G, I, E> Iter<'a, G, I, E>
+where
+    G: Query,
+    I: Query,
+    E: Query,
+{
+    /// Returns an iterator over the remaining items and their dense indexes.
+    /// See [`DenseIter::enumerate_dense`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator is sparse, because sparse items have no common
+    /// dense index.
+    pub fn enumerate_dense(self) -> impl Iterator<Item = (usize, G::Item<'a>)> {
+        match self {
+            Self::Sparse(_) => panic!("Cannot enumerate the dense indexes of a sparse query"),
+            Self::Dense(iter) => iter.enumerate_dense(),
+        }
+    }
+}
+
 impl<'a, G, I, E> Iterator for Iter<'a, G, I, E>
 where
     G: Query,
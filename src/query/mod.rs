@@ -1,21 +1,27 @@
 //! Query and iterate entities and components.
 
+mod either;
 mod iter;
 mod query_all;
 mod query_one;
 mod query_part;
+mod query_plan;
 
 #[cfg(feature = "parallel")]
 mod par_iter;
 
+pub use self::either::Either;
 pub use self::iter::*;
 pub use self::query_all::*;
 pub use self::query_one::*;
 pub use self::query_part::*;
+pub use self::query_plan::*;
 
 #[cfg(feature = "parallel")]
 pub use self::par_iter::*;
 
+pub(crate) use self::either::for_each_either;
+
 use crate::component::QueryGroupInfo;
 use crate::entity::Entity;
 use crate::World;
@@ -77,7 +77,7 @@ where
     }
 }
 
-impl<'a, G> IndexedParallelIterator for DenseParIter<'a, G>
+impl<G> IndexedParallelIterator for DenseParIter<'_, G>
 where
     G: Query,
 {
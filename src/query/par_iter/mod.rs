@@ -22,7 +22,7 @@ where
     Dense(DenseParIter<'a, G>),
 }
 
-impl<'a, G, I, E> ParIter<'a, G, I, E>
+impl<G, I, E> ParIter<'_, G, I, E>
 where
     G: Query,
     I: Query,
@@ -1,6 +1,11 @@
-use crate::query::{DenseIter, Iter, Query, QueryGroupInfo, SparseIter};
+use crate::entity::Entity;
+use crate::query::{DenseIter, Iter, Query, QueryGroupInfo, QueryPlan, SparseIter};
 use crate::World;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
 use core::ops::Range;
+use hashbrown::HashSet;
+use rustc_hash::FxBuildHasher;
 
 #[cfg(feature = "parallel")]
 use {
@@ -9,6 +14,44 @@ use {
 };
 
 /// Queries all items that match a query.
+///
+/// The views are borrowed when the query is created and released when it is
+/// dropped. To iterate the same components many times without borrowing them
+/// again, keep the query itself and call [`iter`](Self::iter) on it as needed:
+///
+/// ```rust
+/// use sparsey::query::QueryAll;
+/// use sparsey::World;
+///
+/// struct Position(i32);
+/// struct Velocity(i32);
+///
+/// struct Movement<'a> {
+///     query: QueryAll<'a, (&'a mut Position, &'a Velocity), (), ()>,
+/// }
+///
+/// impl Movement<'_> {
+///     fn run(&mut self) {
+///         for (position, velocity) in self.query.iter() {
+///             position.0 += velocity.0;
+///         }
+///     }
+/// }
+///
+/// let mut world = World::builder()
+///     .register::<Position>()
+///     .register::<Velocity>()
+///     .build();
+///
+/// world.create((Position(0), Velocity(1)));
+///
+/// let mut movement = Movement {
+///     query: world.query_all(),
+/// };
+///
+/// movement.run();
+/// movement.run();
+/// ```
 #[must_use]
 pub struct QueryAll<'a, G, I, E>
 where
@@ -92,7 +135,7 @@ where
     }
 }
 
-impl<'a, G, I, E> QueryAll<'a, G, I, E>
+impl<G, I, E> QueryAll<'_, G, I, E>
 where
     G: Query,
     I: Query,
@@ -110,18 +153,7 @@ where
             let (include_entities, include_sparse) = I::split_filter_parts(&self.include);
             let (_, exclude_sparse) = E::split_filter_parts(&self.exclude);
 
-            let entities = match (get_entities, include_entities) {
-                (Some(get_entities), Some(include_entities)) => {
-                    if get_entities.len() <= include_entities.len() {
-                        get_entities
-                    } else {
-                        include_entities
-                    }
-                }
-                (Some(get_entities), None) => get_entities,
-                (None, Some(include_entities)) => include_entities,
-                (None, None) => &[],
-            };
+            let entities = shortest_entities(get_entities, include_entities);
 
             Iter::Sparse(SparseIter::new(
                 entities,
@@ -133,6 +165,45 @@ where
         }
     }
 
+    /// Returns an iterator over the items mapped to the given `entities` that
+    /// match the query, in the same order as `entities`. Entities that are not
+    /// in the world are skipped and duplicate entities are only visited once.
+    ///
+    /// Useful when a small set of candidate entities is already known, for
+    /// example from a spatial index.
+    pub fn iter_over<'a>(
+        &'a mut self,
+        entities: &'a [Entity],
+    ) -> impl Iterator<Item = G::Item<'a>> + 'a {
+        let world = self.world;
+        let (_, get_sparse, get_data) = G::split_sparse_parts(&self.get);
+        let (_, include_sparse) = I::split_filter_parts(&self.include);
+        let (_, exclude_sparse) = E::split_filter_parts(&self.exclude);
+
+        // Visiting an entity twice would alias mutable components.
+        let mut visited = HashSet::with_capacity_and_hasher(entities.len(), FxBuildHasher);
+
+        entities.iter().filter_map(move |&entity| {
+            // Filters only check sparse indexes, so stale entities must be
+            // rejected first.
+            if !world.contains_entity(entity) || !visited.insert(entity) {
+                return None;
+            }
+
+            let sparse = entity.sparse();
+
+            if !E::contains_none_raw(exclude_sparse, sparse) {
+                return None;
+            }
+
+            if !I::contains_all_raw(include_sparse, sparse) {
+                return None;
+            }
+
+            unsafe { G::get_sparse_raw(get_sparse, get_data, entity) }
+        })
+    }
+
     /// Calls `f` for all items that match the query.
     pub fn for_each<F>(&mut self, f: F)
     where
@@ -141,6 +212,24 @@ where
         self.iter().for_each(f);
     }
 
+    /// Collects all items that match the query into buckets, keyed by the
+    /// value `f` returns for each item. Items within a bucket keep their
+    /// iteration order.
+    #[must_use]
+    pub fn bucket_by_key<K, F>(&mut self, mut f: F) -> BTreeMap<K, Vec<G::Item<'_>>>
+    where
+        K: Ord,
+        F: FnMut(&G::Item<'_>) -> K,
+    {
+        let mut buckets = BTreeMap::<K, Vec<_>>::new();
+
+        self.iter().for_each(|item| {
+            buckets.entry(f(&item)).or_default().push(item);
+        });
+
+        buckets
+    }
+
     /// Returns a parallel iterator over all items that match the query.
     #[cfg(feature = "parallel")]
     pub fn par_iter(&mut self) -> ParIter<'_, G, I, E> {
@@ -154,18 +243,7 @@ where
             let (include_entities, include_sparse) = I::split_filter_parts(&self.include);
             let (_, exclude_sparse) = E::split_filter_parts(&self.exclude);
 
-            let entities = match (get_entities, include_entities) {
-                (Some(get_entities), Some(include_entities)) => {
-                    if get_entities.len() <= include_entities.len() {
-                        get_entities
-                    } else {
-                        include_entities
-                    }
-                }
-                (Some(get_entities), None) => get_entities,
-                (None, Some(include_entities)) => include_entities,
-                (None, None) => &[],
-            };
+            let entities = shortest_entities(get_entities, include_entities);
 
             ParIter::Sparse(SparseParIter::new(
                 entities,
@@ -187,7 +265,11 @@ where
     }
 
     /// Returns ordered slices of all items that match the query, if the query
-    // is grouped.
+    /// is grouped. Returns [`None`] for queries that iterate sparsely.
+    ///
+    /// The slices are contiguous and index the same entities at the same
+    /// positions, so they can be processed in fixed-size chunks, for example
+    /// with [`slice::chunks_exact`].
     #[must_use]
     pub fn slice(&mut self) -> Option<G::Slice<'_>> {
         let range = self.get_group_range()?;
@@ -197,6 +279,60 @@ where
         unsafe { Some(G::slice_raw(get_parts, entities, range)) }
     }
 
+    /// Returns the number of items that match the query.
+    ///
+    /// Grouped queries return the length of the group range directly, while
+    /// sparse queries check every candidate entity without fetching its
+    /// components.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        if let Some(range) = self.get_group_range() {
+            return range.len();
+        }
+
+        self.sparse_matches().count()
+    }
+
+    /// Returns whether no items match the query.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        if let Some(range) = self.get_group_range() {
+            return range.is_empty();
+        }
+
+        self.sparse_matches().next().is_none()
+    }
+
+    /// Returns how the query iterates its items, without iterating them.
+    ///
+    /// Useful for finding out why a query is not as fast as expected.
+    #[must_use]
+    pub fn explain(&self) -> QueryPlan {
+        if let Some(range) = self.get_group_range() {
+            QueryPlan::Dense(range)
+        } else {
+            let (get_entities, _) = G::split_filter_parts(&self.get);
+            let (include_entities, _) = I::split_filter_parts(&self.include);
+            QueryPlan::Sparse(shortest_entities(get_entities, include_entities).len())
+        }
+    }
+
+    fn sparse_matches(&self) -> impl Iterator<Item = &Entity> + '_ {
+        let (get_entities, get_sparse) = G::split_filter_parts(&self.get);
+        let (include_entities, include_sparse) = I::split_filter_parts(&self.include);
+        let (_, exclude_sparse) = E::split_filter_parts(&self.exclude);
+
+        shortest_entities(get_entities, include_entities)
+            .iter()
+            .filter(move |entity| {
+                let sparse = entity.sparse();
+
+                E::contains_none_raw(exclude_sparse, sparse)
+                    && I::contains_all_raw(include_sparse, sparse)
+                    && G::contains_all_raw(get_sparse, sparse)
+            })
+    }
+
     #[must_use]
     fn get_group_range(&self) -> Option<Range<usize>> {
         let get_info = self.get_info?;
@@ -211,6 +347,25 @@ where
     }
 }
 
+#[must_use]
+fn shortest_entities<'a>(
+    get_entities: Option<&'a [Entity]>,
+    include_entities: Option<&'a [Entity]>,
+) -> &'a [Entity] {
+    match (get_entities, include_entities) {
+        (Some(get_entities), Some(include_entities)) => {
+            if get_entities.len() <= include_entities.len() {
+                get_entities
+            } else {
+                include_entities
+            }
+        }
+        (Some(get_entities), None) => get_entities,
+        (None, Some(include_entities)) => include_entities,
+        (None, None) => &[],
+    }
+}
+
 #[allow(clippy::into_iter_without_iter)]
 impl<'a, G, I, E> IntoIterator for &'a mut QueryAll<'_, G, I, E>
 where
@@ -68,7 +68,7 @@ where
     }
 }
 
-impl<'a, G, I, E> QueryOne<'a, G, I, E>
+impl<G, I, E> QueryOne<'_, G, I, E>
 where
     G: Query,
     I: Query,
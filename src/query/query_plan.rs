@@ -0,0 +1,35 @@
+use core::ops::Range;
+
+/// Describes how a [`QueryAll`](crate::query::QueryAll) iterates its items.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum QueryPlan {
+    /// The query is grouped and iterates the components in the given dense
+    /// range. Extremely fast.
+    Dense(Range<usize>),
+    /// The query is not grouped and checks each entity in the shortest entity
+    /// slice of its views. Holds the length of that slice.
+    Sparse(usize),
+}
+
+impl QueryPlan {
+    /// Returns whether the query iterates sparsely.
+    #[must_use]
+    pub const fn is_sparse(&self) -> bool {
+        matches!(self, Self::Sparse(_))
+    }
+
+    /// Returns whether the query iterates densely.
+    #[must_use]
+    pub const fn is_dense(&self) -> bool {
+        matches!(self, Self::Dense(_))
+    }
+
+    /// Returns the maximum number of items the query can yield.
+    #[must_use]
+    pub fn max_len(&self) -> usize {
+        match self {
+            Self::Dense(range) => range.end - range.start,
+            Self::Sparse(len) => *len,
+        }
+    }
+}
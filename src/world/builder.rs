@@ -1,4 +1,5 @@
 use crate::component::{Component, ComponentData, GroupDescriptor, GroupLayout};
+use crate::entity::EntityStorage;
 use crate::world::World;
 use alloc::vec::Vec;
 
@@ -8,6 +9,9 @@ use alloc::vec::Vec;
 pub struct WorldBuilder {
     layout: GroupLayout,
     components: Vec<ComponentData>,
+    entity_capacity: usize,
+    auto_register: bool,
+    deterministic: bool,
 }
 
 impl WorldBuilder {
@@ -18,6 +22,29 @@ impl WorldBuilder {
         self
     }
 
+    /// Sets the number of entities the world can hold before reallocating.
+    #[inline]
+    pub fn set_entity_capacity(&mut self, entity_capacity: usize) -> &mut Self {
+        self.entity_capacity = entity_capacity;
+        self
+    }
+
+    /// Sets whether the world registers components automatically when they
+    /// are first added to an entity. See [`World::set_auto_register`].
+    #[inline]
+    pub fn set_auto_register(&mut self, auto_register: bool) -> &mut Self {
+        self.auto_register = auto_register;
+        self
+    }
+
+    /// Sets whether atomic entity creation is disabled on the world. See
+    /// [`World::deterministic`].
+    #[inline]
+    pub fn set_deterministic(&mut self, deterministic: bool) -> &mut Self {
+        self.deterministic = deterministic;
+        self
+    }
+
     /// Adds a new component group to the world.
     pub fn add_group<G>(&mut self) -> &mut Self
     where
@@ -55,6 +82,13 @@ impl WorldBuilder {
     pub fn build(&self) -> World {
         let mut world = World::new(&self.layout);
 
+        if self.deterministic {
+            world.entities = EntityStorage::deterministic();
+        }
+
+        world.reserve(self.entity_capacity);
+        world.set_auto_register(self.auto_register);
+
         for &component in &self.components {
             world.register_dyn(component);
         }
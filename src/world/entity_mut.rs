@@ -0,0 +1,80 @@
+use crate::component::ComponentSet;
+use crate::entity::Entity;
+use crate::world::World;
+use core::ops::Deref;
+
+/// Handle to an entity that allows adding and removing components
+/// immediately. Returned by [`World::spawn`].
+#[must_use]
+#[derive(Debug)]
+pub struct EntityMut<'a> {
+    world: &'a mut World,
+    entity: Entity,
+}
+
+impl<'a> EntityMut<'a> {
+    #[inline]
+    pub(crate) fn new(world: &'a mut World, entity: Entity) -> Self {
+        Self { world, entity }
+    }
+
+    /// Returns the entity.
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> Entity {
+        self.entity
+    }
+
+    /// Adds the given `components` to the entity, replacing any components of
+    /// the same types.
+    pub fn insert<C>(&mut self, components: C) -> &mut Self
+    where
+        C: ComponentSet,
+    {
+        unsafe {
+            C::insert(self.world, self.entity, components);
+        }
+
+        self
+    }
+
+    /// Removes components from the entity.
+    ///
+    /// Returns the components that were successfully removed.
+    #[must_use = "Use `delete` to discard the components."]
+    pub fn remove<C>(&mut self) -> C::Remove
+    where
+        C: ComponentSet,
+    {
+        unsafe { C::remove(self.world, self.entity) }
+    }
+
+    /// Removes components from the entity, without returning them.
+    ///
+    /// This is faster than calling [`remove`](Self::remove).
+    pub fn delete<C>(&mut self) -> &mut Self
+    where
+        C: ComponentSet,
+    {
+        unsafe {
+            C::delete(self.world, self.entity);
+        }
+
+        self
+    }
+
+    /// Removes the entity from the world, along with all its components.
+    #[inline]
+    pub fn despawn(self) {
+        self.world.destroy(self.entity);
+    }
+}
+
+impl Deref for EntityMut<'_> {
+    type Target = Entity;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.entity
+    }
+}
@@ -0,0 +1,23 @@
+use core::{error, fmt};
+
+pub use crate::component::BorrowError;
+
+/// Error returned by [`World::try_insert`](crate::World::try_insert).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum InsertError {
+    /// The entity is not in the world.
+    MissingEntity,
+    /// The entity already has at least one of the components.
+    AlreadyPresent,
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingEntity => f.write_str("Entity is not in the world"),
+            Self::AlreadyPresent => f.write_str("Entity already has the components"),
+        }
+    }
+}
+
+impl error::Error for InsertError {}
@@ -0,0 +1,36 @@
+use alloc::vec::Vec;
+
+pub use crate::entity::StorageMemory;
+
+/// Estimate of the memory used by a [`World`](crate::World), broken down by
+/// storage.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MemoryReport {
+    /// Memory used by the entity storage.
+    pub entities: StorageMemory,
+    /// Memory used by each component storage, one per registered component.
+    pub components: Vec<StorageMemory>,
+}
+
+impl MemoryReport {
+    /// Returns the total number of bytes occupied by entities and components.
+    #[must_use]
+    pub fn used_bytes(&self) -> usize {
+        self.components
+            .iter()
+            .fold(self.entities.used_bytes, |bytes, storage| {
+                bytes + storage.used_bytes
+            })
+    }
+
+    /// Returns the total number of bytes allocated for entities and
+    /// components.
+    #[must_use]
+    pub fn allocated_bytes(&self) -> usize {
+        self.components
+            .iter()
+            .fold(self.entities.allocated_bytes, |bytes, storage| {
+                bytes + storage.allocated_bytes
+            })
+    }
+}
@@ -1,14 +1,22 @@
 //! Manage and query entities and their associated components.
 
 mod builder;
+mod entity_mut;
+mod error;
+mod memory;
 
 pub use self::builder::*;
+pub use self::entity_mut::*;
+pub use self::error::*;
+pub use self::memory::*;
+pub use crate::component::{GroupFamilyInfo, SubgroupInfo};
 
 use crate::component::{
     Component, ComponentData, ComponentSet, ComponentStorage, GroupInfo, GroupLayout, View, ViewMut,
 };
-use crate::entity::{Entity, EntityStorage};
-use crate::query::{Query, QueryAll, QueryOne};
+use crate::entity::{AllocatorStats, CreateAtError, Entity, EntityRemap, EntityStorage};
+use crate::query::{for_each_either, Either, Query, QueryAll, QueryOne};
+use alloc::vec::Vec;
 use core::any::TypeId;
 
 /// Collection for entities and their associated components.
@@ -35,6 +43,64 @@ impl World {
         }
     }
 
+    /// Creates a new world with space for at least `entity_capacity` entities
+    /// before reallocating.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(entity_capacity: usize) -> Self {
+        let mut world = Self::default();
+        world.reserve(entity_capacity);
+        world
+    }
+
+    /// Reserves capacity for at least `additional` more entities.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.entities.reserve(additional);
+    }
+
+    /// Creates a new world with atomic entity creation disabled, so calling
+    /// [`create_atomic`](Self::create_atomic) panics.
+    ///
+    /// Entities created through `&mut World` already depend only on the
+    /// sequence of operations. Atomic creation is the only way for entity
+    /// allocation to depend on thread interleaving, so disabling it keeps the
+    /// entities of a world reproducible across runs.
+    #[must_use]
+    pub fn deterministic() -> Self {
+        Self {
+            entities: EntityStorage::deterministic(),
+            components: ComponentStorage::default(),
+        }
+    }
+
+    /// Returns whether the world was created with
+    /// [`deterministic`](Self::deterministic).
+    #[inline]
+    #[must_use]
+    pub fn is_deterministic(&self) -> bool {
+        self.entities.is_deterministic()
+    }
+
+    /// Sets whether components are registered automatically when they are
+    /// first added to an entity with [`create`](Self::create),
+    /// [`extend`](Self::extend) or [`insert`](Self::insert).
+    ///
+    /// Automatically registered components are not part of any group. Other
+    /// operations still panic on unregistered components.
+    #[inline]
+    pub fn set_auto_register(&mut self, auto_register: bool) {
+        self.components.auto_register = auto_register;
+    }
+
+    /// Returns whether components are registered automatically when they are
+    /// first added to an entity.
+    #[inline]
+    #[must_use]
+    pub fn is_auto_register(&self) -> bool {
+        self.components.auto_register
+    }
+
     /// Sets a new group `layout` on this world.
     ///
     /// This operation requires iteration over all entities in the world, so it
@@ -48,6 +114,9 @@ impl World {
 
     /// Registers a new component type on this world.
     ///
+    /// Registering a component that is already registered has no effect, so
+    /// the storage and grouping of grouped components are left untouched.
+    ///
     /// Returns whether the component was newly registered.
     pub fn register<T>(&mut self) -> bool
     where
@@ -80,7 +149,8 @@ impl World {
         self.components.is_registered_dyn(component)
     }
 
-    /// Creates a new entity with the given `components`.
+    /// Creates a new entity with the given `components`. Entities queued with
+    /// [`create_atomic`](Self::create_atomic) are added to the world first.
     ///
     /// Returns the newly created entity.
     pub fn create<C>(&mut self, components: C) -> Entity
@@ -96,7 +166,42 @@ impl World {
         entity
     }
 
+    /// Creates a new entity without any components and returns a handle for
+    /// adding and removing its components immediately.
+    pub fn spawn(&mut self) -> EntityMut<'_> {
+        let entity = self.entities.create();
+        EntityMut::new(self, entity)
+    }
+
+    /// Creates the given `entity` with the given `components`, keeping its
+    /// index and version. Useful for restoring entities from a save file.
+    ///
+    /// Entities created afterwards never reuse the index of `entity` while it
+    /// is alive. Indexes skipped over by `entity` are stored as a single range
+    /// and handed out by later calls to [`create`](Self::create). Storages
+    /// still grow with the largest entity index, so indexes read from
+    /// untrusted sources should be bounded by the caller.
+    ///
+    /// Returns an error if an entity with the same index is already in the
+    /// world, if the version of `entity` is older than the version its index
+    /// would be reused with, or if the index can't be reused. In all of these
+    /// cases the world is left unchanged.
+    pub fn create_at<C>(&mut self, entity: Entity, components: C) -> Result<(), CreateAtError>
+    where
+        C: ComponentSet,
+    {
+        self.entities.create_at(entity)?;
+
+        unsafe {
+            C::insert(self, entity, components);
+        }
+
+        Ok(())
+    }
+
     /// Creates new entities with the `components` produced by the iterator.
+    /// Entities queued with [`create_atomic`](Self::create_atomic) are added
+    /// to the world first.
     ///
     /// Returns the newly created entities as a slice.
     pub fn extend<C, I>(&mut self, components: I) -> &[Entity]
@@ -107,6 +212,30 @@ impl World {
         C::extend(self, components)
     }
 
+    /// Moves all entities and components from `other` into this world,
+    /// registering any component types that are missing.
+    ///
+    /// The moved entities are recreated in this world, so they get new ids.
+    /// Returns a map from the entities in `other` to the new entities, which
+    /// can be used to fix up stored entity references.
+    ///
+    /// Components are regrouped according to the layout of this world, which
+    /// requires one grouping pass per moved entity.
+    pub fn merge(&mut self, mut other: World) -> EntityRemap {
+        other.maintain();
+
+        let mut remap = EntityRemap::default();
+        remap.reserve(other.entities.len());
+        self.entities.reserve(other.entities.len());
+
+        for &entity in other.entities.as_slice() {
+            remap.insert(entity, self.entities.create());
+        }
+
+        self.components.merge(&mut other.components, &remap);
+        remap
+    }
+
     /// Removes the `entity` and its associated components from the world.
     ///
     /// Returns whether the operation was successfull, i.e. whether the entity
@@ -121,11 +250,61 @@ impl World {
         true
     }
 
+    /// Removes all entities that have a component of type `T`, along with
+    /// their associated components.
+    ///
+    /// Returns the number of entities removed.
+    pub fn destroy_all_with<T>(&mut self) -> usize
+    where
+        T: Component,
+    {
+        // Destroying entities reorders the dense arrays, so iterate a copy.
+        let entities = self.borrow::<T>().entities().to_vec();
+
+        for &entity in &entities {
+            self.destroy(entity);
+        }
+
+        entities.len()
+    }
+
+    /// Removes all entities with a component of type `T` for which `f` returns
+    /// `false`, along with their associated components. Entities without a
+    /// component of type `T` are kept.
+    ///
+    /// Returns the number of entities removed.
+    pub fn retain<T, F>(&mut self, mut f: F) -> usize
+    where
+        T: Component,
+        F: FnMut(Entity, &T) -> bool,
+    {
+        let entities = {
+            let view = self.borrow::<T>();
+
+            view.entities()
+                .iter()
+                .zip(view.as_slice())
+                .filter(|&(&entity, component)| !f(entity, component))
+                .map(|(&entity, _)| entity)
+                .collect::<Vec<_>>()
+        };
+
+        for &entity in &entities {
+            self.destroy(entity);
+        }
+
+        entities.len()
+    }
+
     /// Queues the creation of an entity without requiring exclusive access to
     /// the world. Entities created with this method can be added to the world
     /// by calling [`maintain`](Self::maintain).
     ///
     /// Returns the entity to be created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world is [`deterministic`](Self::deterministic).
     #[inline]
     pub fn create_atomic(&self) -> Entity {
         self.entities.create_atomic()
@@ -133,6 +312,11 @@ impl World {
 
     /// Adds the entities created with [`create_atomic`](Self::create_atomic)
     /// to the world.
+    ///
+    /// Queued entities are not part of the world until this method is called,
+    /// so components can't be inserted on them and queries don't return them.
+    /// [`create`](Self::create) and [`extend`](Self::extend) also add queued
+    /// entities to the world.
     #[inline]
     pub fn maintain(&mut self) {
         self.entities.maintain();
@@ -141,21 +325,76 @@ impl World {
     /// Inserts `components` to an existing `entity`, overwriting previous data
     /// if necessary.
     ///
-    /// Returns whether the `components` were added successfully, i.e. whether
-    /// the `entity` existed in the world before this call.
-    pub fn insert<C>(&mut self, entity: Entity, components: C) -> bool
+    /// Returns the components that were replaced, or `None` if the `entity`
+    /// is not in the world, in which case no components are inserted.
+    pub fn insert<C>(&mut self, entity: Entity, components: C) -> Option<C::Remove>
     where
         C: ComponentSet,
     {
         if !self.entities.contains(entity) {
-            return false;
+            return None;
+        }
+
+        unsafe { Some(C::insert(self, entity, components)) }
+    }
+
+    /// Inserts `components` to an existing `entity`, without overwriting any
+    /// of its components.
+    ///
+    /// Returns an error if the `entity` is not in the world or if it already
+    /// has any of the `components`, in which case no components are inserted.
+    pub fn try_insert<C>(&mut self, entity: Entity, components: C) -> Result<(), InsertError>
+    where
+        C: ComponentSet,
+    {
+        if !self.entities.contains(entity) {
+            return Err(InsertError::MissingEntity);
+        }
+
+        if C::contains_any(self, entity) {
+            return Err(InsertError::AlreadyPresent);
         }
 
         unsafe {
             C::insert(self, entity, components);
         }
 
-        true
+        Ok(())
+    }
+
+    /// Inserts or overwrites a component of type `T` for each entity produced
+    /// by the iterator. Entities that are not in the world are skipped.
+    ///
+    /// Entities are grouped once all components are inserted. Like
+    /// [`insert`](Self::insert), registers `T` first if auto-registration is
+    /// enabled.
+    pub fn set_components<T, I>(&mut self, components: I)
+    where
+        T: Component,
+        I: IntoIterator<Item = (Entity, T)>,
+    {
+        let entities = &self.entities;
+
+        self.components.insert_many(
+            components
+                .into_iter()
+                .filter(|&(entity, _)| entities.contains(entity)),
+        );
+    }
+
+    /// Inserts or overwrites the component of type `T` of the `entity`.
+    ///
+    /// Returns the replaced component, if any. Returns [`None`] without
+    /// inserting the component if the `entity` is not in the world.
+    pub fn replace<T>(&mut self, entity: Entity, component: T) -> Option<T>
+    where
+        T: Component,
+    {
+        if !self.entities.contains(entity) {
+            return None;
+        }
+
+        self.components.insert(entity, component)
     }
 
     /// Removes components from the `entity`, returning the removed components
@@ -168,6 +407,26 @@ impl World {
         unsafe { C::remove(self, entity) }
     }
 
+    /// Removes components from the `entity` and then removes the `entity` from
+    /// the world, along with its remaining components.
+    ///
+    /// Returns the removed components if the `entity` existed and had all of
+    /// them. If any component is missing, returns [`None`] and leaves the
+    /// `entity` and its components untouched.
+    #[must_use = "Use `destroy` to discard the components."]
+    pub fn take_entity_components<C>(&mut self, entity: Entity) -> Option<C>
+    where
+        C: ComponentSet,
+    {
+        if !self.entities.contains(entity) {
+            return None;
+        }
+
+        let components = unsafe { C::take(self, entity)? };
+        self.destroy(entity);
+        Some(components)
+    }
+
     /// Removes components from the `entity`, without returning them.
     ///
     /// This is faster than calling [`remove`](Self::remove).
@@ -181,7 +440,7 @@ impl World {
     }
 
     /// Queries an entity with the given components.
-    pub fn query_one<G>(&self) -> QueryOne<G, (), ()>
+    pub fn query_one<G>(&self) -> QueryOne<'_, G, (), ()>
     where
         G: Query,
     {
@@ -189,7 +448,7 @@ impl World {
     }
 
     /// Queries all entities with the given components.
-    pub fn query_all<G>(&self) -> QueryAll<G, (), ()>
+    pub fn query_all<G>(&self) -> QueryAll<'_, G, (), ()>
     where
         G: Query,
     {
@@ -213,6 +472,34 @@ impl World {
         self.query_all().for_each(f);
     }
 
+    /// Iterates over all entities that match either of the queries `L` and
+    /// `R`. Entities matching both queries are visited once, with both items.
+    ///
+    /// Unions are always iterated sparsely, so they don't benefit from
+    /// grouping. Entities matching both queries are skipped during the second
+    /// pass with a sparse lookup, so no allocations are made.
+    pub fn for_each_either<L, R>(&self, f: impl FnMut(Either<L::Item<'_>, R::Item<'_>>))
+    where
+        L: Query,
+        R: Query,
+    {
+        for_each_either::<L, R, _>(self, f);
+    }
+
+    /// Calls `f` for every component of type `T` and the entity that owns it.
+    pub fn for_each_mut<T, F>(&mut self, mut f: F)
+    where
+        T: Component,
+        F: FnMut(Entity, &mut T),
+    {
+        let mut view = self.borrow_mut::<T>();
+        let (entities, components) = view.split_mut();
+
+        for (&entity, component) in entities.iter().zip(components) {
+            f(entity, component);
+        }
+    }
+
     /// Iterates in parallel over all entities with the given components.
     #[cfg(feature = "parallel")]
     pub fn par_for_each<G>(&self, f: impl Fn(G::Item<'_>) + Send + Sync)
@@ -230,12 +517,134 @@ impl World {
     }
 
     /// Returns all entities in the world as a slice.
+    ///
+    /// The order of the entities is unspecified and may change when entities
+    /// are created or destroyed. Use [`entities_sorted`](Self::entities_sorted)
+    /// for a stable order.
     #[inline]
     #[must_use]
     pub fn entities(&self) -> &[Entity] {
         self.entities.as_slice()
     }
 
+    /// Returns an iterator over all entities in the world, in slices of at most
+    /// `chunk_size` entities, in the same order as [`entities`](Self::entities).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[inline]
+    pub fn entity_chunks(&self, chunk_size: usize) -> impl Iterator<Item = &[Entity]> {
+        self.entities.as_slice().chunks(chunk_size)
+    }
+
+    /// Calls `f` for every entity in the world.
+    #[inline]
+    pub fn for_each_entity<F>(&self, f: F)
+    where
+        F: FnMut(Entity),
+    {
+        self.entities.as_slice().iter().copied().for_each(f);
+    }
+
+    /// Returns all components of type `T` as a slice, or an empty slice if the
+    /// component is not registered.
+    ///
+    /// Requires exclusive access to the world, so no view needs to be borrowed.
+    #[must_use]
+    pub fn components<T>(&mut self) -> &[T]
+    where
+        T: Component,
+    {
+        match self.components.get_mut::<T>() {
+            Some(sparse_set) => unsafe { sparse_set.as_slice::<T>() },
+            None => &[],
+        }
+    }
+
+    /// Returns all entities with a component of type `T` as a slice, in the
+    /// same order as [`components`](Self::components), or an empty slice if the
+    /// component is not registered.
+    #[must_use]
+    pub fn entities_with<T>(&mut self) -> &[Entity]
+    where
+        T: Component,
+    {
+        match self.components.get_mut::<T>() {
+            Some(sparse_set) => sparse_set.entities(),
+            None => &[],
+        }
+    }
+
+    /// Returns the entities with a component of type `T` and the components
+    /// themselves as parallel slices, or [`None`] if the component is not
+    /// registered.
+    #[must_use]
+    pub fn components_and_entities<T>(&mut self) -> Option<(&[Entity], &[T])>
+    where
+        T: Component,
+    {
+        let sparse_set = self.components.get_mut::<T>()?;
+        unsafe { Some((sparse_set.entities(), sparse_set.as_slice::<T>())) }
+    }
+
+    /// Returns all entities in the world, sorted by index.
+    #[must_use]
+    pub fn entities_sorted(&self) -> Vec<Entity> {
+        let mut entities = self.entities.as_slice().to_vec();
+        entities.sort_unstable_by_key(|entity| entity.index);
+        entities
+    }
+
+    /// Returns the number of components of type `T` in the world, or `0` if
+    /// `T` is not registered.
+    #[must_use]
+    pub fn component_count<T>(&self) -> usize
+    where
+        T: Component,
+    {
+        if !self.is_registered::<T>() {
+            return 0;
+        }
+
+        self.components.borrow::<T>().len()
+    }
+
+    /// Returns an estimate of the memory used by the world's entities and
+    /// components. Heap allocations made by the components themselves are not
+    /// included.
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            entities: self.entities.memory_usage(),
+            components: self.components.memory_usage(),
+        }
+    }
+
+    /// Releases the memory the storage of components of type `T` allocated
+    /// beyond what its current components need, for example after destroying
+    /// many entities. The order of the components is preserved.
+    pub fn defrag<T>(&mut self)
+    where
+        T: Component,
+    {
+        self.components.shrink_to_fit::<T>();
+    }
+
+    /// Returns the group families of the world, in layout order, along with
+    /// the number of entities in each group.
+    #[must_use]
+    pub fn group_families(&self) -> Vec<GroupFamilyInfo> {
+        self.components.group_families()
+    }
+
+    /// Returns statistics about the entities allocated by the world.
+    #[inline]
+    #[must_use]
+    pub fn entity_allocator_stats(&self) -> AllocatorStats {
+        self.entities.allocator_stats()
+    }
+
     /// Returns whether the world contains no entities.
     #[inline]
     #[must_use]
@@ -259,8 +668,13 @@ impl World {
     }
 
     /// Returns a shared view over all components of type `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component is not registered or is already borrowed
+    /// mutably. See [`try_borrow`](Self::try_borrow) for a fallible version.
     #[must_use]
-    pub fn borrow<T>(&self) -> View<T>
+    pub fn borrow<T>(&self) -> View<'_, T>
     where
         T: Component,
     {
@@ -268,18 +682,41 @@ impl World {
     }
 
     /// Returns an exclusive view over all components of type `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component is not registered or is already borrowed. See
+    /// [`try_borrow_mut`](Self::try_borrow_mut) for a fallible version.
     #[must_use]
-    pub fn borrow_mut<T>(&self) -> ViewMut<T>
+    pub fn borrow_mut<T>(&self) -> ViewMut<'_, T>
     where
         T: Component,
     {
         self.components.borrow_mut::<T>()
     }
 
+    /// Returns a shared view over all components of type `T`, or an error if
+    /// the component is not registered or is already borrowed mutably.
+    pub fn try_borrow<T>(&self) -> Result<View<'_, T>, BorrowError>
+    where
+        T: Component,
+    {
+        self.components.try_borrow::<T>()
+    }
+
+    /// Returns an exclusive view over all components of type `T`, or an error
+    /// if the component is not registered or is already borrowed.
+    pub fn try_borrow_mut<T>(&self) -> Result<ViewMut<'_, T>, BorrowError>
+    where
+        T: Component,
+    {
+        self.components.try_borrow_mut::<T>()
+    }
+
     /// Returns a shared view over all components of type `T`, along with
     /// grouping information.
     #[must_use]
-    pub fn borrow_with_group_info<T>(&self) -> (View<T>, Option<GroupInfo>)
+    pub fn borrow_with_group_info<T>(&self) -> (View<'_, T>, Option<GroupInfo>)
     where
         T: Component,
     {
@@ -289,7 +726,7 @@ impl World {
     /// Returns an exclusive view over all components of type `T`, along with
     /// grouping information.
     #[must_use]
-    pub fn borrow_with_group_info_mut<T>(&self) -> (ViewMut<T>, Option<GroupInfo>)
+    pub fn borrow_with_group_info_mut<T>(&self) -> (ViewMut<'_, T>, Option<GroupInfo>)
     where
         T: Component,
     {
@@ -1,3 +1,5 @@
+#![allow(dead_code, missing_docs)]
+
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct A(pub u32);
 
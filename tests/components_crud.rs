@@ -1,6 +1,10 @@
+#![allow(missing_docs)]
+
 mod common;
 
 use common::*;
+use sparsey::entity::Entity;
+use sparsey::world::{BorrowError, InsertError};
 use sparsey::World;
 
 #[test]
@@ -51,3 +55,431 @@ fn test_components_crud() {
     assert_eq!(world.query_one::<&A>().get(e0), None);
     assert_eq!(world.query_one::<&B>().get(e0), None);
 }
+
+#[test]
+fn test_for_each_mut() {
+    let mut world = World::default();
+    world.register::<A>();
+
+    let e0 = world.create((A(0),));
+    let e1 = world.create((A(1),));
+    let e2 = world.create(());
+
+    let mut visited = Vec::new();
+    world.for_each_mut::<A, _>(|entity, a| {
+        visited.push(entity);
+        a.0 += 10;
+    });
+
+    assert_eq!(visited, [e0, e1]);
+    assert_eq!(world.query_one::<&A>().get(e0), Some(&A(10)));
+    assert_eq!(world.query_one::<&A>().get(e1), Some(&A(11)));
+    assert_eq!(world.query_one::<&A>().get(e2), None);
+}
+
+#[test]
+fn test_view_to_vec() {
+    let mut world = World::default();
+    world.register::<A>();
+
+    let e0 = world.create((A(0),));
+    let e1 = world.create((A(1),));
+    let e2 = world.create((A(2),));
+    world.destroy(e0);
+
+    let view = world.borrow::<A>();
+    let mut components = view.to_vec();
+    components.sort();
+    assert_eq!(components, [(e1, A(1)), (e2, A(2))]);
+
+    assert!(view.contains_all(&[(e2, A(2)), (e1, A(1))]));
+    assert!(!view.contains_all(&[(e1, A(2))]));
+    assert!(!view.contains_all(&[(e0, A(0))]));
+}
+
+#[test]
+#[should_panic = "is already borrowed"]
+fn test_aliasing_mutable_borrow() {
+    let mut world = World::default();
+    world.register::<A>();
+    world.for_each::<(&mut A, &mut A)>(|_| ());
+}
+
+#[test]
+#[should_panic = "is already borrowed mutably"]
+fn test_aliasing_shared_borrow() {
+    let mut world = World::default();
+    world.register::<A>();
+    world.for_each::<(&mut A, &A)>(|_| ());
+}
+
+#[test]
+fn test_memory_usage() {
+    let mut world = World::default();
+    assert_eq!(world.component_count::<A>(), 0);
+
+    world.register::<A>();
+    world.register::<B>();
+
+    world.create((A(0),));
+    world.create((A(1), B(1)));
+    assert_eq!(world.component_count::<A>(), 2);
+    assert_eq!(world.component_count::<B>(), 1);
+
+    let report = world.memory_usage();
+    assert_eq!(report.entities.len, 2);
+    assert_eq!(report.components.len(), 2);
+
+    let a = report
+        .components
+        .iter()
+        .find(|storage| storage.type_name.ends_with("::A"))
+        .unwrap();
+
+    assert_eq!(a.len, 2);
+    assert!(a.capacity >= a.len);
+    assert!(a.allocated_bytes >= a.used_bytes);
+    assert!(report.allocated_bytes() >= report.used_bytes());
+}
+
+#[test]
+fn test_sort_dense_by() {
+    let mut world = World::default();
+    world.register::<A>();
+
+    let entities = [3, 1, 4, 1, 5, 9, 2, 6]
+        .into_iter()
+        .map(|i| world.create((A(i),)))
+        .collect::<Vec<_>>();
+
+    let mut view = world.borrow_mut::<A>();
+    view.sort_dense_by(|_, a| a.0);
+
+    let values = view.as_slice().iter().map(|a| a.0).collect::<Vec<_>>();
+    assert_eq!(values, [1, 1, 2, 3, 4, 5, 6, 9]);
+
+    for (&entity, a) in view.entities().iter().zip(view.as_slice()) {
+        assert_eq!(view.get(entity), Some(a));
+    }
+
+    assert_eq!(view[entities[5]], A(9));
+}
+
+#[test]
+#[should_panic = "Cannot sort grouped component"]
+fn test_sort_dense_by_grouped() {
+    let world = World::builder().add_group::<(A, B)>().build();
+    world.borrow_mut::<A>().sort_dense_by(|entity, _| entity);
+}
+
+#[test]
+fn test_take_entity_components() {
+    let mut world = World::default();
+    world.register::<A>();
+    world.register::<B>();
+    world.register::<C>();
+
+    let e0 = world.create((A(0), B(0), C(0)));
+    let e1 = world.create((A(1),));
+
+    assert_eq!(
+        world.take_entity_components::<(A, B)>(e0),
+        Some((A(0), B(0)))
+    );
+    assert!(!world.contains_entity(e0));
+    assert!(world.borrow::<C>().is_empty());
+
+    assert_eq!(world.take_entity_components::<(A, B)>(e1), None);
+    assert!(world.contains_entity(e1));
+    assert_eq!(world.borrow::<A>().get(e1), Some(&A(1)));
+
+    assert_eq!(world.take_entity_components::<(A,)>(e1), Some((A(1),)));
+    assert!(!world.contains_entity(e1));
+    assert_eq!(world.take_entity_components::<(A,)>(e1), None);
+}
+
+#[test]
+fn test_try_borrow() {
+    let mut world = World::default();
+    world.register::<A>();
+
+    assert_eq!(
+        world.try_borrow::<B>().err(),
+        Some(BorrowError::Unregistered)
+    );
+    assert_eq!(
+        world.try_borrow_mut::<B>().err(),
+        Some(BorrowError::Unregistered)
+    );
+
+    let view = world.try_borrow::<A>().unwrap();
+    assert!(world.try_borrow::<A>().is_ok());
+    assert_eq!(
+        world.try_borrow_mut::<A>().err(),
+        Some(BorrowError::AlreadyBorrowed)
+    );
+    drop(view);
+
+    let view = world.try_borrow_mut::<A>().unwrap();
+    assert_eq!(
+        world.try_borrow::<A>().err(),
+        Some(BorrowError::AlreadyBorrowed)
+    );
+    assert_eq!(
+        world.try_borrow_mut::<A>().err(),
+        Some(BorrowError::AlreadyBorrowed)
+    );
+    drop(view);
+}
+
+#[test]
+fn test_insert_replaced() {
+    let mut world = World::default();
+    world.register::<A>();
+    world.register::<B>();
+
+    let e0 = world.create((A(0),));
+    assert_eq!(world.insert(e0, (A(1), B(1))), Some((Some(A(0)), None)));
+    assert_eq!(world.borrow::<A>().get(e0), Some(&A(1)));
+    assert_eq!(world.borrow::<B>().get(e0), Some(&B(1)));
+
+    world.destroy(e0);
+    assert_eq!(world.insert(e0, (A(2),)), None);
+}
+
+#[test]
+fn test_try_insert() {
+    let mut world = World::default();
+    world.register::<A>();
+    world.register::<B>();
+
+    let e0 = world.create((A(0),));
+    assert_eq!(
+        world.try_insert(e0, (A(1), B(1))),
+        Err(InsertError::AlreadyPresent)
+    );
+    assert_eq!(world.borrow::<A>().get(e0), Some(&A(0)));
+    assert!(!world.borrow::<B>().contains(e0));
+
+    assert_eq!(world.try_insert(e0, (B(1),)), Ok(()));
+    assert_eq!(world.borrow::<B>().get(e0), Some(&B(1)));
+
+    world.destroy(e0);
+    assert_eq!(
+        world.try_insert(e0, (B(2),)),
+        Err(InsertError::MissingEntity)
+    );
+}
+
+#[test]
+fn test_concurrent_shared_borrows() {
+    let mut world = World::default();
+    world.register::<A>();
+    world.create((A(1),));
+    world.create((A(2),));
+
+    let view = world.borrow::<A>();
+
+    let sums = std::thread::scope(|scope| {
+        let handles = [(); 2].map(|()| {
+            scope.spawn(|| {
+                let view = world.borrow::<A>();
+                view.as_slice().iter().map(|a| a.0).sum::<u32>()
+            })
+        });
+
+        handles.map(|handle| handle.join().unwrap())
+    });
+
+    assert_eq!(sums, [3, 3]);
+    assert_eq!(view.len(), 2);
+}
+
+#[test]
+fn test_set_components() {
+    let mut world = World::builder().add_group::<(A, B)>().build();
+
+    let e0 = world.create((A(0), B(0)));
+    let e1 = world.create((A(1),));
+    let e2 = world.create((A(2),));
+    let e3 = world.create((A(3),));
+    world.destroy(e3);
+
+    world.set_components([(e0, B(10)), (e1, B(11)), (e3, B(13))]);
+
+    let view = world.borrow::<B>();
+    assert_eq!(view.get(e0), Some(&B(10)));
+    assert_eq!(view.get(e1), Some(&B(11)));
+    assert_eq!(view.get(e2), None);
+    assert_eq!(view.len(), 2);
+    drop(view);
+
+    let mut query = world.query_all::<Entity>().include::<(&A, &B)>();
+    assert!(query.iter().is_dense());
+    assert_eq!(query.iter().count(), 2);
+}
+
+#[test]
+fn test_defrag() {
+    let mut world = World::builder().add_group::<(A, B)>().build();
+
+    let entities = world.extend((0..100).map(|i| (A(i), B(i)))).to_vec();
+
+    for &entity in &entities[10..] {
+        world.destroy(entity);
+    }
+
+    world.defrag::<A>();
+
+    let report = world.memory_usage();
+    let a = report
+        .components
+        .iter()
+        .find(|storage| storage.type_name.ends_with("::A"))
+        .unwrap();
+
+    assert_eq!(a.capacity, 10);
+    assert_eq!(a.allocated_bytes, a.used_bytes);
+
+    for (i, &entity) in entities[..10].iter().enumerate() {
+        assert_eq!(world.borrow::<A>().get(entity), Some(&A(i as u32)));
+    }
+
+    let mut query = world.query_all::<(&A, &B)>();
+    assert!(query.iter().is_dense());
+    assert_eq!(query.iter().count(), 10);
+    drop(query);
+
+    for &entity in &entities[..10] {
+        world.destroy(entity);
+    }
+
+    world.defrag::<A>();
+
+    let report = world.memory_usage();
+    let a = report
+        .components
+        .iter()
+        .find(|storage| storage.type_name.ends_with("::A"))
+        .unwrap();
+
+    assert_eq!(a.allocated_bytes, 0);
+}
+
+#[test]
+fn test_dense_index() {
+    let mut world = World::default();
+    world.register::<A>();
+
+    let e0 = world.create((A(0),));
+    let e1 = world.create((A(1),));
+    let e2 = world.create(());
+    world.destroy(e0);
+
+    let view = world.borrow::<A>();
+    let index = view.dense_index(e1).unwrap();
+    assert_eq!(view.entities()[index], e1);
+    assert_eq!(view.as_slice()[index], A(1));
+    assert_eq!(view.dense_index(e0), None);
+    assert_eq!(view.dense_index(e2), None);
+}
+
+#[test]
+fn test_components_slice() {
+    let mut world = World::default();
+    world.register::<A>();
+
+    let e0 = world.create((A(0),));
+    let e1 = world.create((A(1),));
+
+    assert_eq!(world.components::<A>(), [A(0), A(1)]);
+    assert_eq!(world.entities_with::<A>(), [e0, e1]);
+    assert_eq!(world.components::<B>(), []);
+    assert_eq!(world.entities_with::<B>(), []);
+}
+
+#[test]
+fn test_components_and_entities() {
+    let mut world = World::default();
+    world.register::<A>();
+
+    let e0 = world.create((A(0),));
+    let e1 = world.create((A(1),));
+
+    assert_eq!(
+        world.components_and_entities::<A>(),
+        Some((&[e0, e1][..], &[A(0), A(1)][..]))
+    );
+    assert_eq!(world.components_and_entities::<B>(), None);
+}
+
+#[test]
+fn test_spawn() {
+    let mut world = World::default();
+    world.register::<A>();
+    world.register::<B>();
+
+    let e0 = world.spawn().insert((A(0),)).insert((B(0),)).id();
+    assert_eq!(world.query_one::<(&A, &B)>().get(e0), Some((&A(0), &B(0))));
+
+    let mut entity = world.spawn();
+    entity.insert((A(1), B(1)));
+    assert_eq!(entity.remove::<(B,)>(), (Some(B(1)),));
+    let e1 = *entity;
+    entity.despawn();
+
+    assert!(!world.contains_entity(e1));
+    assert_eq!(world.entities(), [e0]);
+
+    let e2 = world.spawn().insert((A(2), B(2))).delete::<(A,)>().id();
+    assert!(!world.contains::<&A>(e2));
+    assert!(world.contains::<&B>(e2));
+}
+
+#[test]
+fn test_replace() {
+    let mut world = World::builder().add_group::<(A, B)>().build();
+
+    let e0 = world.create((A(0),));
+    assert_eq!(world.replace(e0, B(0)), None);
+    assert_eq!(world.replace(e0, B(1)), Some(B(0)));
+    assert_eq!(world.borrow::<B>().get(e0), Some(&B(1)));
+
+    let mut query = world.query_all::<(&A, &B)>();
+    assert!(query.iter().is_dense());
+    assert_eq!(query.iter().count(), 1);
+    drop(query);
+
+    world.destroy(e0);
+    assert_eq!(world.replace(e0, B(2)), None);
+    assert!(world.borrow::<B>().is_empty());
+}
+
+#[test]
+fn test_set_components_auto_register() {
+    let mut world = World::default();
+    world.set_auto_register(true);
+
+    let e0 = world.create(());
+    world.set_components([(e0, A(0))]);
+    assert_eq!(world.replace(e0, B(0)), None);
+
+    assert_eq!(world.borrow::<A>().get(e0), Some(&A(0)));
+    assert_eq!(world.borrow::<B>().get(e0), Some(&B(0)));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_view_par_iter() {
+    use rayon::iter::ParallelIterator;
+
+    let mut world = World::default();
+    world.register::<A>();
+    world.extend((0..100).map(|i| (A(i),)));
+
+    let view = world.borrow::<A>();
+    let entities = view.par_entities().copied().collect::<Vec<_>>();
+    let components = view.par_components().copied().collect::<Vec<_>>();
+    assert_eq!(entities, view.entities());
+    assert_eq!(components, view.as_slice());
+}
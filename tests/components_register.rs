@@ -1,3 +1,5 @@
+#![allow(missing_docs)]
+
 mod common;
 
 use self::common::*;
@@ -12,14 +14,18 @@ fn test_components_register() {
     assert!(!world.is_registered::<B>());
 
     // Register component A
-    world.register::<A>();
+    assert!(world.register::<A>());
     assert!(world.is_registered::<A>());
 
     // Register component B
-    world.register::<B>();
+    assert!(world.register::<B>());
     assert!(world.is_registered::<A>());
     assert!(world.is_registered::<B>());
 
+    // Registering a component again has no effect
+    assert!(!world.register::<A>());
+    assert!(world.is_registered::<A>());
+
     // Components remain registered even after clear
     world.clear();
     assert!(world.is_registered::<A>());
@@ -30,3 +36,45 @@ fn test_components_register() {
     assert!(world.is_registered::<A>());
     assert!(world.is_registered::<B>());
 }
+
+#[test]
+fn test_auto_register() {
+    let mut world = World::builder().set_auto_register(true).build();
+    assert!(world.is_auto_register());
+
+    let e0 = world.create((A(0),));
+    world.extend([(B(1),), (B(2),)]);
+    world.insert(e0, (C(0),));
+
+    assert!(world.is_registered::<A>());
+    assert!(world.is_registered::<B>());
+    assert!(world.is_registered::<C>());
+    assert_eq!(world.borrow::<A>().get(e0), Some(&A(0)));
+    assert_eq!(world.borrow::<B>().len(), 2);
+    assert_eq!(world.borrow::<C>().get(e0), Some(&C(0)));
+}
+
+#[test]
+#[should_panic = "was not registered"]
+fn test_auto_register_disabled() {
+    let mut world = World::default();
+    world.create((A(0),));
+}
+
+#[test]
+fn test_register_grouped_again() {
+    let mut world = World::builder().add_group::<(A, B)>().build();
+    let e0 = world.create((A(0), B(0)));
+
+    assert!(!world.register::<A>());
+    assert!(!world.register::<B>());
+
+    let mut query = world.query_all::<(&A, &B)>();
+    let mut iter = query.iter();
+    assert!(iter.is_dense());
+    assert_eq!(iter.next(), Some((&A(0), &B(0))));
+    assert_eq!(iter.next(), None);
+    drop(query);
+
+    assert_eq!(world.borrow::<A>().get(e0), Some(&A(0)));
+}
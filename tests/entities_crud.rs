@@ -1,3 +1,6 @@
+#![allow(missing_docs)]
+
+use sparsey::entity::{AllocatorStats, CreateAtError, Entity, Version};
 use sparsey::World;
 
 #[test]
@@ -38,3 +41,231 @@ fn test_entities_crud() {
     assert!(!world.contains_entity(e1));
     assert_eq!(world.entities(), &[]);
 }
+
+#[test]
+fn test_entity_allocator_stats() {
+    let mut world = World::default();
+    assert_eq!(world.entity_allocator_stats(), AllocatorStats::default());
+
+    let e0 = world.create(());
+    let _e1 = world.create(());
+    world.destroy(e0);
+
+    let stats = world.entity_allocator_stats();
+    assert_eq!(stats.live, 1);
+    assert_eq!(stats.recycled, 1);
+    assert_eq!(stats.high_water_mark, 2);
+
+    // Recycled entities are reused before allocating new indexes
+    let _e2 = world.create(());
+    let stats = world.entity_allocator_stats();
+    assert_eq!(stats.live, 2);
+    assert_eq!(stats.recycled, 0);
+    assert_eq!(stats.high_water_mark, 2);
+}
+
+#[test]
+fn test_entity_capacity() {
+    let mut world = World::with_capacity(100);
+    assert!(world.memory_usage().entities.capacity >= 100);
+
+    world.reserve(1000);
+    assert!(world.memory_usage().entities.capacity >= 1000);
+
+    let world = World::builder().set_entity_capacity(100).build();
+    assert!(world.memory_usage().entities.capacity >= 100);
+}
+
+#[test]
+fn test_builder_deterministic() {
+    let mut world = World::builder()
+        .set_deterministic(true)
+        .add_group::<(u8, u16)>()
+        .build();
+
+    assert!(world.is_deterministic());
+    assert!(!World::builder().build().is_deterministic());
+
+    world.create((0_u8, 0_u16));
+    assert_eq!(world.group_families()[0].groups[0].len, 1);
+}
+
+#[test]
+#[should_panic = "deterministic"]
+fn test_deterministic_disables_create_atomic() {
+    let world = World::deterministic();
+    assert!(world.is_deterministic());
+    let _ = world.create_atomic();
+}
+
+#[test]
+fn test_maintain_after_create() {
+    let mut world = World::default();
+    let e0 = world.create(());
+    world.destroy(e0);
+    let e1 = world.create(());
+    let e2 = world.create(());
+    world.destroy(e2);
+
+    world.maintain();
+    assert_eq!(world.entities(), &[e1]);
+    assert!(!world.contains_entity(e0));
+    assert!(!world.contains_entity(e2));
+}
+
+#[test]
+fn test_entities_sorted() {
+    let mut world = World::default();
+    let e0 = world.create(());
+    let e1 = world.create(());
+    let e2 = world.create(());
+
+    world.destroy(e0);
+    let e3 = world.create(());
+    assert_eq!(e3.index, e0.index);
+
+    assert_eq!(world.entities_sorted(), [e3, e1, e2]);
+}
+
+#[test]
+fn test_create_atomic_maintain() {
+    let mut world = World::default();
+    let e0 = world.create_atomic();
+    assert!(!world.contains_entity(e0));
+    assert_eq!(world.insert(e0, ()), None);
+
+    world.maintain();
+    assert!(world.contains_entity(e0));
+    assert_eq!(world.insert(e0, ()), Some(()));
+    assert_eq!(world.entities(), &[e0]);
+}
+
+#[test]
+fn test_entity_display() {
+    let entity = Entity::new(12, Version::FIRST.next().unwrap());
+    assert_eq!(entity.to_string(), "12v2");
+}
+
+#[test]
+fn test_create_at() {
+    let mut world = World::default();
+    let e0 = world.create(());
+    let e5 = Entity::new(5, Version::FIRST.next().unwrap());
+
+    assert_eq!(world.create_at(e5, ()), Ok(()));
+    assert!(world.contains_entity(e5));
+    assert_eq!(
+        world.create_at(Entity::with_index(5), ()),
+        Err(CreateAtError::Exists(e5))
+    );
+    assert_eq!(
+        world.create_at(Entity::with_index(0), ()),
+        Err(CreateAtError::Exists(e0))
+    );
+
+    // Skipped indexes are reused before new ones are allocated
+    let mut indexes = (0..5).map(|_| world.create(()).index).collect::<Vec<_>>();
+    indexes.sort_unstable();
+    assert_eq!(indexes, [1, 2, 3, 4, 6]);
+
+    // Recycled indexes can't be restored with an older version
+    world.destroy(e0);
+    let recycled = Entity::new(0, Version::FIRST.next().unwrap());
+    assert_eq!(
+        world.create_at(e0, ()),
+        Err(CreateAtError::StaleVersion(recycled))
+    );
+    assert!(!world.contains_entity(e0));
+
+    // Recycled indexes can be restored with a newer version
+    let restored = Entity::new(0, recycled.version.next().unwrap());
+    assert_eq!(world.create_at(restored, ()), Ok(()));
+    assert_ne!(world.create(()).index, 0);
+
+    // Indexes with no versions left can't be reused
+    let last = Entity::new(10, Version::LAST);
+    assert_eq!(world.create_at(last, ()), Ok(()));
+    world.destroy(last);
+    assert_eq!(world.create_at(last, ()), Err(CreateAtError::Retired));
+}
+
+#[test]
+fn test_create_at_skipped_indexes() {
+    let mut world = World::default();
+    let e0 = Entity::with_index(1_000_000);
+    let e1 = Entity::with_index(500_000);
+
+    assert_eq!(world.create_at(e0, ()), Ok(()));
+    assert_eq!(world.create_at(e1, ()), Ok(()));
+    assert!(world.contains_entity(e0));
+    assert!(world.contains_entity(e1));
+
+    let stats = world.entity_allocator_stats();
+    assert_eq!(stats.recycled, 0);
+    assert_eq!(stats.high_water_mark, 2);
+
+    let entities = (0..3).map(|_| world.create(())).collect::<Vec<_>>();
+    assert!(entities.iter().all(|entity| entity.index < e0.index));
+    assert!(!entities.contains(&e1));
+    assert_eq!(world.entity_allocator_stats().high_water_mark, 5);
+}
+
+#[test]
+fn test_for_each_entity() {
+    let mut world = World::default();
+    let e0 = world.create(());
+    let e1 = world.create(());
+    let e2 = world.create(());
+    world.destroy(e1);
+
+    let mut entities = Vec::new();
+    world.for_each_entity(|entity| entities.push(entity));
+    entities.sort_unstable_by_key(|entity| entity.index);
+    assert_eq!(entities, [e0, e2]);
+}
+
+#[test]
+fn test_contains_recycled_entity() {
+    let mut world = World::default();
+
+    let e0 = world.create(());
+    world.destroy(e0);
+    world.maintain();
+
+    let e1 = world.create(());
+    assert_eq!(e1.index, e0.index);
+    assert_ne!(e1.version, e0.version);
+    assert!(world.contains_entity(e1));
+    assert!(!world.contains_entity(e0));
+}
+
+#[test]
+fn test_entity_chunks() {
+    let mut world = World::default();
+    let entities = world.extend((0..5).map(|_| ())).to_vec();
+
+    let chunks = world.entity_chunks(2).collect::<Vec<_>>();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks.concat(), entities);
+    assert!(chunks.iter().all(|chunk| chunk.len() <= 2));
+}
+
+#[test]
+fn test_create_at_recycled() {
+    let mut world = World::default();
+    let entities = world.extend((0..5).map(|_| ())).to_vec();
+
+    for &entity in &entities {
+        world.destroy(entity);
+    }
+
+    for &i in &[1, 3] {
+        let restored = Entity::new(entities[i].index, entities[i].version.next().unwrap());
+        assert_eq!(world.create_at(restored, ()), Ok(()));
+    }
+
+    let mut indexes = (0..4).map(|_| world.create(()).index).collect::<Vec<_>>();
+    indexes.sort_unstable();
+    assert_eq!(indexes, [0, 2, 4, 5]);
+    assert_eq!(world.entity_allocator_stats().recycled, 0);
+}
@@ -0,0 +1,68 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use common::*;
+use sparsey::entity::Entity;
+use sparsey::World;
+
+#[test]
+fn test_destroy_all_with() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    let e0 = world.create((A(0), B(0)));
+    let e1 = world.create((A(1), B(1), C(1)));
+    let e2 = world.create((A(2), C(2)));
+    let e3 = world.create((B(3),));
+
+    // Destroy all entities with component C
+    assert_eq!(world.destroy_all_with::<C>(), 2);
+    assert!(world.contains_entity(e0));
+    assert!(!world.contains_entity(e1));
+    assert!(!world.contains_entity(e2));
+    assert!(world.contains_entity(e3));
+    assert_eq!(world.borrow::<C>().len(), 0);
+
+    // Groups remain consistent after destroying grouped entities
+    {
+        let mut query = world.query_all::<Entity>().include::<(&A, &B)>();
+        let mut iter = query.iter();
+        assert!(iter.is_dense());
+        assert_eq!(iter.next(), Some(e0));
+        assert_eq!(iter.next(), None);
+    }
+
+    // Destroying with no matching entities is a no-op
+    assert_eq!(world.destroy_all_with::<C>(), 0);
+    assert_eq!(world.entities().len(), 2);
+}
+
+#[test]
+fn test_retain() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    let e0 = world.create((A(0), B(0)));
+    let e1 = world.create((A(1), B(1)));
+    let e2 = world.create((A(2),));
+    let e3 = world.create((C(3),));
+
+    // Keep entities with a non-zero A
+    assert_eq!(world.retain::<A, _>(|_, a| a.0 > 0), 1);
+    assert!(!world.contains_entity(e0));
+    assert!(world.contains_entity(e1));
+    assert!(world.contains_entity(e2));
+    assert!(world.contains_entity(e3));
+
+    // Groups remain consistent after destroying grouped entities
+    let mut query = world.query_all::<Entity>().include::<(&A, &B)>();
+    let mut iter = query.iter();
+    assert!(iter.is_dense());
+    assert_eq!(iter.next(), Some(e1));
+    assert_eq!(iter.next(), None);
+}
@@ -0,0 +1,123 @@
+#![allow(missing_docs)]
+
+use sparsey::component::{ComponentData, GroupLayout, MAX_GROUP_ARITY};
+use sparsey::entity::Entity;
+use sparsey::World;
+
+struct X<const F: usize, const I: usize>;
+
+#[rustfmt::skip]
+fn family<const F: usize>() -> [ComponentData; MAX_GROUP_ARITY] {
+    [
+        ComponentData::new::<X<F, 0>>(), ComponentData::new::<X<F, 1>>(),
+        ComponentData::new::<X<F, 2>>(), ComponentData::new::<X<F, 3>>(),
+        ComponentData::new::<X<F, 4>>(), ComponentData::new::<X<F, 5>>(),
+        ComponentData::new::<X<F, 6>>(), ComponentData::new::<X<F, 7>>(),
+        ComponentData::new::<X<F, 8>>(), ComponentData::new::<X<F, 9>>(),
+        ComponentData::new::<X<F, 10>>(), ComponentData::new::<X<F, 11>>(),
+        ComponentData::new::<X<F, 12>>(), ComponentData::new::<X<F, 13>>(),
+        ComponentData::new::<X<F, 14>>(), ComponentData::new::<X<F, 15>>(),
+    ]
+}
+
+fn add_family(layout: &mut GroupLayout, components: &[ComponentData]) {
+    for arity in 2..=components.len() {
+        layout.add_group_dyn(&components[..arity]);
+    }
+}
+
+#[test]
+fn test_max_group_arity() {
+    let mut layout = GroupLayout::default();
+    add_family(&mut layout, &family::<0>());
+    assert_eq!(layout.group_count(), MAX_GROUP_ARITY - 1);
+
+    let mut world = World::new(&layout);
+    #[rustfmt::skip]
+    let e0 = world.create((
+        X::<0, 0>, X::<0, 1>, X::<0, 2>, X::<0, 3>,
+        X::<0, 4>, X::<0, 5>, X::<0, 6>, X::<0, 7>,
+        X::<0, 8>, X::<0, 9>, X::<0, 10>, X::<0, 11>,
+        X::<0, 12>, X::<0, 13>, X::<0, 14>, X::<0, 15>,
+    ));
+
+    #[rustfmt::skip]
+    let mut query = world.query_all::<Entity>().include::<(
+        &X<0, 0>, &X<0, 1>, &X<0, 2>, &X<0, 3>,
+        &X<0, 4>, &X<0, 5>, &X<0, 6>, &X<0, 7>,
+        &X<0, 8>, &X<0, 9>, &X<0, 10>, &X<0, 11>,
+        &X<0, 12>, &X<0, 13>, &X<0, 14>, &X<0, 15>,
+    )>();
+
+    assert!(query.explain().is_dense());
+    assert_eq!(query.iter().collect::<Vec<_>>(), [e0]);
+}
+
+#[test]
+#[should_panic = "Group layouts must have at most"]
+fn test_max_group_count() {
+    let mut layout = GroupLayout::default();
+    add_family(&mut layout, &family::<0>());
+    add_family(&mut layout, &family::<1>());
+    add_family(&mut layout, &family::<2>());
+    add_family(&mut layout, &family::<3>());
+    add_family(&mut layout, &family::<4>());
+}
+
+#[test]
+fn test_same_group_family() {
+    let world = World::builder()
+        .add_group::<(X<0, 0>, X<0, 1>)>()
+        .add_group::<(X<0, 0>, X<0, 1>, X<0, 2>)>()
+        .add_group::<(X<1, 0>, X<1, 1>)>()
+        .register::<X<2, 0>>()
+        .build();
+
+    let (_, a) = world.borrow_with_group_info::<X<0, 0>>();
+    let (_, b) = world.borrow_with_group_info::<X<0, 2>>();
+    let (_, c) = world.borrow_with_group_info::<X<1, 0>>();
+    let (_, d) = world.borrow_with_group_info::<X<2, 0>>();
+
+    let (a, b, c) = (a.unwrap(), b.unwrap(), c.unwrap());
+    assert!(a.same_group_family(&b));
+    assert!(!a.same_group_family(&c));
+    assert!(d.is_none());
+}
+
+#[test]
+fn test_group_families() {
+    let mut world = World::builder()
+        .add_group::<(X<0, 0>, X<0, 1>)>()
+        .add_group::<(X<0, 0>, X<0, 1>, X<0, 2>)>()
+        .add_group::<(X<1, 0>, X<1, 1>)>()
+        .register::<X<2, 0>>()
+        .build();
+
+    world.create((X::<0, 0>, X::<0, 1>));
+    world.create((X::<0, 0>, X::<0, 1>, X::<0, 2>));
+
+    let families = world.group_families();
+    assert_eq!(families.len(), 2);
+
+    let groups = &families[0].groups;
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].components.len(), 2);
+    assert_eq!(groups[0].len, 2);
+    assert_eq!(groups[1].components.len(), 3);
+    assert_eq!(groups[1].len, 1);
+    assert_eq!(groups[1].components[..2], groups[0].components);
+
+    let groups = &families[1].groups;
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].len, 0);
+}
+
+#[test]
+#[should_panic = "Groups families may not have any component types in common"]
+fn test_overlapping_group_families() {
+    let components = family::<0>();
+    let mut layout = GroupLayout::default();
+    layout.add_group_dyn(&components[0..2]);
+    layout.add_group_dyn(&components[2..4]);
+    layout.add_group_dyn(&components[0..4]);
+}
@@ -1,11 +1,12 @@
+#![allow(missing_docs)]
+
 mod common;
 
 use common::*;
 use sparsey::entity::Entity;
-use sparsey::query::Query;
+use sparsey::query::{Either, Query, QueryPlan};
 use sparsey::World;
 use std::collections::HashSet;
-use std::iter::FromIterator;
 
 #[test]
 fn test_sparse() {
@@ -48,6 +49,225 @@ fn test_dense() {
     test_iter::<(&A, &B, &C), &D>(world, true, &[e1]);
 }
 
+#[test]
+fn test_dense_extend() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .add_group::<(A, B, C)>()
+        .build();
+
+    let e0 = world.create((A(0),));
+    let e1 = world.extend((0..2).map(|i| (A(i), B(i)))).to_vec();
+    let e2 = world.extend((0..2).map(|i| (A(i), B(i), C(i)))).to_vec();
+    let e3 = world.extend((0..2).map(|i| (A(i), B(i)))).to_vec();
+    world.insert(e0, (B(0),));
+
+    let world = &mut world;
+    let ab = [&e1[..], &e2[..], &e3[..], &[e0]].concat();
+    test_iter::<(&A, &B), ()>(world, true, &ab);
+    test_iter::<(&A, &B, &C), ()>(world, true, &e2);
+    test_iter::<(&A, &B), &C>(world, true, &[&e1[..], &e3[..], &[e0]].concat());
+}
+
+#[test]
+fn test_slice() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    let e0 = world.create((A(0), B(0)));
+    let e1 = world.create((A(1), B(1), C(1)));
+    world.create((A(2), C(2)));
+
+    let mut query = world.query_all::<(Entity, &A, &B)>();
+    let (entities, a, b) = query.slice().unwrap();
+    assert_eq!(entities, [e0, e1]);
+    assert_eq!(a, [A(0), A(1)]);
+    assert_eq!(b, [B(0), B(1)]);
+
+    assert!(world.query_all::<(&A, &C)>().slice().is_none());
+}
+
+#[test]
+fn test_bucket_by_key() {
+    let mut world = World::builder().register::<A>().register::<B>().build();
+    let e0 = world.create((A(0), B(0)));
+    let e1 = world.create((A(1), B(1)));
+    let e2 = world.create((A(2),));
+    let e3 = world.create((A(3), B(3)));
+
+    let mut query = world.query_all::<(Entity, &A)>();
+    let buckets = query.bucket_by_key(|(_, a)| a.0 % 2);
+    assert_eq!(buckets.len(), 2);
+    assert_eq!(buckets[&0], [(e0, &A(0)), (e2, &A(2))]);
+    assert_eq!(buckets[&1], [(e1, &A(1)), (e3, &A(3))]);
+
+    let mut query = world.query_all::<Entity>().include::<&B>();
+    let buckets = query.bucket_by_key(|entity| *entity == e1);
+    assert_eq!(buckets[&false], [e0, e3]);
+    assert_eq!(buckets[&true], [e1]);
+}
+
+#[test]
+fn test_for_each() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    let e0 = world.create((A(0), B(0)));
+    let e1 = world.create((A(1), B(1), C(1)));
+    let e2 = world.create((A(2), C(2)));
+
+    let mut entities = HashSet::new();
+    world.for_each::<(Entity, &A, &B)>(|(entity, _, _)| {
+        entities.insert(entity);
+    });
+    assert_eq!(entities, HashSet::from([e0, e1]));
+
+    let mut entities = HashSet::new();
+    world.for_each::<(Entity, &A, &C)>(|(entity, _, _)| {
+        entities.insert(entity);
+    });
+    assert_eq!(entities, HashSet::from([e1, e2]));
+}
+
+#[test]
+fn test_explain() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    world.create((A(0), B(0)));
+    world.create((A(1), B(1), C(1)));
+    world.create((A(2),));
+
+    let plan = world.query_all::<(&A, &B)>().explain();
+    assert_eq!(plan, QueryPlan::Dense(0..2));
+
+    let plan = world.query_all::<&A>().include::<&C>().explain();
+    assert_eq!(plan, QueryPlan::Sparse(1));
+
+    let plan = world.query_all::<&A>().exclude::<&C>().explain();
+    assert_eq!(plan, QueryPlan::Sparse(3));
+}
+
+#[test]
+fn test_iter_over() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    let e0 = world.create((A(0), B(0)));
+    let e1 = world.create((A(1), B(1), C(1)));
+    let e2 = world.create((A(2),));
+    let e3 = world.create((A(3), B(3)));
+    world.destroy(e3);
+
+    {
+        let mut query = world
+            .query_all::<(Entity, &A)>()
+            .include::<&B>()
+            .exclude::<&C>();
+        let candidates = [e3, e2, e1, e0];
+        let items = query.iter_over(&candidates).collect::<Vec<_>>();
+        assert_eq!(items, [(e0, &A(0))]);
+    }
+
+    {
+        let mut query = world.query_all::<Entity>();
+        let candidates = [e2, e3, e0, e2];
+        let items = query.iter_over(&candidates).collect::<Vec<_>>();
+        assert_eq!(items, [e2, e0]);
+    }
+
+    let mut query = world.query_all::<&mut A>();
+    let candidates = [e1, e1];
+    assert_eq!(query.iter_over(&candidates).count(), 1);
+}
+
+#[test]
+fn test_count() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    world.create((A(0), B(0)));
+    world.create((A(1), B(1), C(1)));
+    world.create((A(2), C(2)));
+
+    let query = world.query_all::<(&A, &B)>();
+    assert_eq!(query.count(), 2);
+    assert!(!query.is_empty());
+
+    let query = world.query_all::<&A>().include::<&C>().exclude::<&B>();
+    assert_eq!(query.count(), 1);
+    assert!(!query.is_empty());
+
+    let query = world.query_all::<&C>().exclude::<&A>();
+    assert_eq!(query.count(), 0);
+    assert!(query.is_empty());
+}
+
+#[test]
+fn test_fused() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    let e0 = world.create((A(0), B(0), C(0)));
+
+    let mut query = world.query_all::<Entity>().include::<(&A, &B)>();
+    let mut iter = query.iter();
+    assert!(iter.is_dense());
+    assert_eq!(iter.next(), Some(e0));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+
+    let mut query = world.query_all::<Entity>().include::<(&A, &C)>();
+    let mut iter = query.iter();
+    assert!(iter.is_sparse());
+    assert_eq!(iter.next(), Some(e0));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_enumerate_dense() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    world.create((A(0), B(0)));
+    world.create((A(1),));
+    world.create((A(2), B(2)));
+
+    let a = world.borrow::<A>();
+    let b = world.borrow::<B>();
+
+    let mut query = world.query_all::<(&A, &B)>();
+    let items = query.iter().enumerate_dense().collect::<Vec<_>>();
+    assert_eq!(items.len(), 2);
+
+    for (i, (item_a, item_b)) in items {
+        assert_eq!(&a.as_slice()[i], item_a);
+        assert_eq!(&b.as_slice()[i], item_b);
+    }
+}
+
+#[test]
+#[should_panic = "Cannot enumerate the dense indexes of a sparse query"]
+fn test_enumerate_dense_sparse() {
+    let world = World::builder().register::<A>().register::<C>().build();
+    let _ = world.query_all::<(&A, &C)>().iter().enumerate_dense();
+}
+
 #[track_caller]
 fn test_iter<I, E>(world: &World, is_dense: bool, expected_entities: &[Entity])
 where
@@ -62,6 +282,60 @@ where
     let entities = iter.collect::<HashSet<_>>();
     assert_eq!(
         entities,
-        HashSet::from_iter(expected_entities.iter().copied()),
+        expected_entities.iter().copied().collect::<HashSet<_>>(),
     );
 }
+
+#[test]
+fn test_for_each_either() {
+    let mut world = World::builder()
+        .add_group::<(A, B)>()
+        .register::<C>()
+        .build();
+
+    let e0 = world.create((A(0), B(0)));
+    let e1 = world.create((A(1), C(1)));
+    let e2 = world.create((C(2),));
+    world.create((B(3),));
+
+    let mut items = Vec::new();
+    world.for_each_either::<(Entity, &A), (Entity, &C)>(|item| {
+        items.push(match item {
+            Either::Left((entity, a)) => (entity, Some(*a), None),
+            Either::Right((entity, c)) => (entity, None, Some(*c)),
+            Either::Both((entity, a), (_, c)) => (entity, Some(*a), Some(*c)),
+        });
+    });
+
+    items.sort();
+    assert_eq!(
+        items,
+        [
+            (e0, Some(A(0)), None),
+            (e1, Some(A(1)), Some(C(1))),
+            (e2, None, Some(C(2))),
+        ],
+    );
+}
+
+#[test]
+fn test_for_each_with_side_view() {
+    let mut world = World::default();
+    world.register::<A>();
+    world.register::<B>();
+    world.register::<C>();
+
+    let e0 = world.create((A(0), B(1), C(10)));
+    let e1 = world.create((A(0), B(2)));
+
+    let c = world.borrow::<C>();
+    world
+        .query_all::<(Entity, &mut A, &B)>()
+        .for_each(|(entity, a, b)| {
+            a.0 = b.0 + c.get(entity).map_or(0, |c| c.0);
+        });
+    drop(c);
+
+    assert_eq!(world.borrow::<A>().get(e0), Some(&A(11)));
+    assert_eq!(world.borrow::<A>().get(e1), Some(&A(2)));
+}
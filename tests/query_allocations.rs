@@ -0,0 +1,64 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use common::*;
+use sparsey::World;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_sparse_query_does_not_allocate() {
+    let mut world = World::default();
+    world.register::<A>();
+    world.register::<B>();
+    world.register::<C>();
+
+    for i in 0..100 {
+        world.create((A(i), B(i)));
+        world.create((A(i), C(i)));
+    }
+
+    let start = allocations();
+
+    for _ in 0..10 {
+        let mut sum = 0;
+
+        world
+            .query_all::<(&A, &mut B)>()
+            .include::<&A>()
+            .exclude::<&C>()
+            .for_each(|(a, b)| {
+                b.0 += 1;
+                sum += a.0;
+            });
+
+        assert_eq!(sum, (0..100).sum());
+    }
+
+    assert_eq!(allocations(), start);
+}
@@ -0,0 +1,67 @@
+#![allow(missing_docs)]
+
+use sparsey::entity::{Entity, SparseVec, SparseVecSlot, Version};
+
+#[test]
+fn test_sparse_vec() {
+    let mut sparse = SparseVec::new();
+    let e0 = Entity::with_index(3);
+
+    *sparse.get_mut_or_allocate_at(e0.sparse()) = Some(SparseVecSlot {
+        index: 0,
+        version: e0.version,
+    });
+
+    assert!(sparse.contains(e0));
+    assert_eq!(sparse.get(e0), Some(0));
+    assert_eq!(unsafe { sparse.get_sparse_unchecked(e0.sparse()) }, 0);
+
+    let e1 = Entity::new(3, Version::FIRST.next().unwrap());
+    assert!(!sparse.contains(e1));
+    assert_eq!(sparse.remove(e1), None);
+    assert_eq!(sparse.remove(e0), Some(0));
+    assert!(!sparse.contains_sparse(e0.sparse()));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic = "is not present in the sparse vec"]
+fn test_get_sparse_unchecked_missing() {
+    let mut sparse = SparseVec::new();
+    let _ = sparse.get_mut_or_allocate_at(7);
+    let _ = unsafe { sparse.get_sparse_unchecked(2) };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic = "is out of bounds"]
+fn test_get_unchecked_mut_out_of_bounds() {
+    let mut sparse = SparseVec::new();
+    let _ = unsafe { sparse.get_unchecked_mut(2) };
+}
+
+#[test]
+fn test_sparse_vec_iter() {
+    let mut sparse = SparseVec::new();
+    let entities = [
+        Entity::with_index(5),
+        Entity::with_index(1),
+        Entity::with_index(8),
+    ];
+
+    for (index, entity) in entities.iter().enumerate() {
+        *sparse.get_mut_or_allocate_at(entity.sparse()) = Some(SparseVecSlot {
+            index: index as u32,
+            version: entity.version,
+        });
+    }
+
+    sparse.remove(entities[0]);
+
+    let slots = sparse
+        .iter()
+        .map(|(sparse, slot)| (sparse, slot.index))
+        .collect::<Vec<_>>();
+
+    assert_eq!(slots, [(1, 1), (8, 2)]);
+}
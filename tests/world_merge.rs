@@ -0,0 +1,39 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use common::*;
+use sparsey::entity::Entity;
+use sparsey::World;
+
+#[test]
+fn test_merge() {
+    let mut world = World::builder().add_group::<(A, B)>().build();
+    let e0 = world.create((A(0), B(0)));
+
+    let mut other = World::builder().register::<A>().register::<C>().build();
+    let o0 = other.create((A(1),));
+    let o1 = other.create((A(2), C(2)));
+    other.destroy(o0);
+    let o2 = other.create((C(3),));
+
+    let remap = world.merge(other);
+    assert_eq!(remap.len(), 2);
+    assert_eq!(remap.get(o0), None);
+
+    let n1 = remap.get(o1).unwrap();
+    let n2 = remap.get(o2).unwrap();
+    assert_eq!(world.entities(), &[e0, n1, n2]);
+
+    // Missing component types are registered
+    assert!(world.is_registered::<C>());
+    assert_eq!(world.query_one::<(&A, &C)>().get(n1), Some((&A(2), &C(2))));
+    assert_eq!(world.query_one::<&C>().get(n2), Some(&C(3)));
+    assert!(!world.contains::<&A>(n2));
+
+    // Merged entities are grouped
+    world.insert(n1, (B(2),));
+    let mut query = world.query_all::<Entity>().include::<(&A, &B)>();
+    assert!(query.explain().is_dense());
+    assert_eq!(query.iter().count(), 2);
+}